@@ -1,7 +1,9 @@
 #![no_std]
 
+use dob_oracle_client::OracleFailure;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, contracterror, token, Address, Env, IntoVal, Symbol, Vec,
+    contract, contractimpl, contracttype, contracterror, token, Address, BytesN, Env, IntoVal,
+    Symbol, Vec,
 };
 
 /// Integer square root using Newton's method
@@ -26,6 +28,7 @@ pub enum DataKey {
     UsdcToken,             // USDC token contract address
     Oracle,                // Oracle contract address
     Operator,              // Operator receiving revenues
+    Admin,                 // Admin upgrading the contract, separate from the operator
     TotalLpShares,         // Total LP shares issued
     LpShares(Address),     // LP shares per address
     LiquidNodes,           // Vec<Address> of registered Liquid Nodes
@@ -34,6 +37,12 @@ pub enum DataKey {
     TotalBought,           // Total USDC spent on buys
     TotalSold,             // Total DOB sold
     DexFeeCollected,       // Total DEX fee collected (1%)
+    TwapWindow,            // Seconds of oracle history averaged for sells and mints
+    Heartbeat,             // Max age in seconds of an oracle price we trade against
+    PendingOperator,       // OperatorProposal awaiting acceptance
+    PendingAdmin,          // AdminProposal awaiting acceptance
+    TtlConfig,             // Storage TTL thresholds, defaults when unset
+    SchemaVersion,         // Storage layout version applied by migrate()
 }
 
 /// LP provision event
@@ -92,7 +101,7 @@ pub struct LnQuote {
 
 /// Swap quote for user
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SwapQuote {
     pub usdc_out: i128,
     pub total_fee_bps: u32,
@@ -100,6 +109,33 @@ pub struct SwapQuote {
     pub from_liquid_nodes: i128,
 }
 
+/// Pending operator handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// Pending admin handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// Ledgers before expiry at which entries are extended, and the TTL they are
+/// extended to; persistent values apply to LP share balances
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    pub instance_threshold: u32,
+    pub instance_extend_to: u32,
+    pub persistent_threshold: u32,
+    pub persistent_extend_to: u32,
+}
+
 /// Errors
 #[contracterror]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -112,12 +148,36 @@ pub enum Error {
     Unauthorized = 6,
     AlreadyRegistered = 7,
     NotRegistered = 8,
+    StalePrice = 9,
+    OraclePaused = 10,
+    NoProposal = 11,
+    ProposalExpired = 12,
+    IssuanceClosed = 13,
+    AlreadyMigrated = 14,
+    InvalidTtl = 15,
+}
+
+impl From<OracleFailure> for Error {
+    fn from(failure: OracleFailure) -> Self {
+        match failure {
+            OracleFailure::Stale => Error::StalePrice,
+            OracleFailure::Paused => Error::OraclePaused,
+        }
+    }
 }
 
 // Constants
 const OPERATOR_SHARE: u32 = 99; // 99% to operator on buys
 const DEX_FEE: u32 = 100; // 1% DEX fee in basis points
 const BPS: u32 = 10000; // Basis points denominator
+const DEFAULT_TWAP_WINDOW: u64 = 3600; // 1 hour of oracle history
+const DEFAULT_HEARTBEAT: u64 = 86_400; // Reject oracle prices older than 1 day
+const DAY_IN_LEDGERS: u32 = 17280; // ~5s per ledger
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const SCHEMA_VERSION: u32 = 1; // Bump together with a migrate() step
 
 #[contract]
 pub struct AmmPool;
@@ -125,8 +185,10 @@ pub struct AmmPool;
 #[contractimpl]
 impl AmmPool {
     /// Initialize the AMM pool contract
+    /// The admin upgrades the pool; the operator collects fees and manages Liquid Nodes
     pub fn initialize(
         env: Env,
+        admin: Address,
         dob_token: Address,
         usdc_token: Address,
         oracle: Address,
@@ -136,10 +198,14 @@ impl AmmPool {
             panic!("Already initialized");
         }
 
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::DobToken, &dob_token);
         env.storage().instance().set(&DataKey::UsdcToken, &usdc_token);
         env.storage().instance().set(&DataKey::Oracle, &oracle);
         env.storage().instance().set(&DataKey::Operator, &operator);
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
         env.storage().instance().set(&DataKey::TotalLpShares, &0i128);
         env.storage().instance().set(&DataKey::UsdcReserve, &0i128);
         env.storage().instance().set(&DataKey::DobReserve, &0i128);
@@ -240,6 +306,8 @@ impl AmmPool {
     ) -> Result<i128, Error> {
         provider.require_auth();
 
+        Self::extend_instance(&env);
+
         if usdc_amount <= 0 || dob_amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -289,6 +357,7 @@ impl AmmPool {
         env.storage()
             .persistent()
             .set(&DataKey::LpShares(provider.clone()), &(provider_shares + lp_shares));
+        Self::extend_persistent(&env, &DataKey::LpShares(provider.clone()));
 
         env.storage().instance().set(&DataKey::TotalLpShares, &(total_lp + lp_shares));
 
@@ -314,6 +383,8 @@ impl AmmPool {
     ) -> Result<(i128, i128), Error> {
         provider.require_auth();
 
+        Self::extend_instance(&env);
+
         if lp_shares <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -347,6 +418,7 @@ impl AmmPool {
         env.storage()
             .persistent()
             .set(&DataKey::LpShares(provider.clone()), &(provider_shares - lp_shares));
+        Self::extend_persistent(&env, &DataKey::LpShares(provider.clone()));
 
         env.storage().instance().set(&DataKey::TotalLpShares, &(total_lp - lp_shares));
 
@@ -371,10 +443,12 @@ impl AmmPool {
     }
 
     /// Buy DOB tokens with USDC (AfterSwap hook)
-    /// Mints new tokens at fair price, sends USDC to operator
+    /// Mints new tokens at the higher of spot and TWAP fair price, sends USDC to operator
     pub fn swap_buy(env: Env, buyer: Address, usdc_amount: i128) -> Result<i128, Error> {
         buyer.require_auth();
 
+        Self::extend_instance(&env);
+
         if usdc_amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -384,8 +458,10 @@ impl AmmPool {
         let oracle: Address = env.storage().instance().get(&DataKey::Oracle).unwrap();
         let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
 
-        // Get fair price from oracle
-        let fair_price: i128 = env.invoke_contract(&oracle, &Symbol::new(&env, "fair_price"), soroban_sdk::vec![&env]);
+        // Mint at the higher of the spot fair price and its TWAP, rejecting a stale feed,
+        // so a single low push cannot be used to mint cheaply
+        let fair_price = Self::mint_price(&env, &oracle, &dob_token)?;
+        Self::ensure_issuance_open(&env, &oracle, &dob_token)?;
 
         // Transfer USDC from buyer to contract
         let usdc_client = token::Client::new(&env, &usdc_token);
@@ -404,7 +480,7 @@ impl AmmPool {
         let dob_amount = (operator_amount * 10_000_000) / fair_price;
 
         // AfterSwap: Mint DOB tokens to buyer
-        let mint_args: Vec<soroban_sdk::Val> = (buyer.clone(), dob_amount).into_val(&env);
+        let mint_args: Vec<soroban_sdk::Val> = (env.current_contract_address(), buyer.clone(), dob_amount).into_val(&env);
         let _: () = env.invoke_contract(
             &dob_token,
             &Symbol::new(&env, "mint"),
@@ -447,6 +523,8 @@ impl AmmPool {
     pub fn swap_sell(env: Env, seller: Address, dob_amount: i128) -> Result<i128, Error> {
         seller.require_auth();
 
+        Self::extend_instance(&env);
+
         if dob_amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -455,13 +533,9 @@ impl AmmPool {
         let usdc_token: Address = env.storage().instance().get(&DataKey::UsdcToken).unwrap();
         let oracle: Address = env.storage().instance().get(&DataKey::Oracle).unwrap();
 
-        // Get fair price and risk from oracle
-        let fair_price: i128 = env.invoke_contract(&oracle, &Symbol::new(&env, "fair_price"), soroban_sdk::vec![&env]);
-        let risk: u32 = env.invoke_contract(&oracle, &Symbol::new(&env, "default_risk"), soroban_sdk::vec![&env]);
-
-        // Calculate base fee from oracle
-        let base_fee_bps = 300 + (risk / 10); // 3% base + risk/10
-        let base_fee_bps = if base_fee_bps > 5000 { 5000 } else { base_fee_bps };
+        // Sell against the time-weighted fair price and current risk (or the
+        // recovery price once impaired), only while the feed is within its heartbeat
+        let (fair_price, base_fee_bps) = Self::redemption_pricing(&env, &oracle, &dob_token)?;
 
         // Calculate how much USDC needed at fair price
         let usdc_needed_at_fair_price = (dob_amount * fair_price) / 10_000_000;
@@ -585,10 +659,10 @@ impl AmmPool {
         let total_usdc_out = from_pool + from_liquid_nodes;
 
         // Burn DOB tokens from this contract (not from seller, since we already transferred them)
-        let burn_args: Vec<soroban_sdk::Val> = (env.current_contract_address(), dob_amount).into_val(&env);
+        let burn_args: Vec<soroban_sdk::Val> = (env.current_contract_address(), env.current_contract_address(), dob_amount).into_val(&env);
         let _: () = env.invoke_contract(
             &dob_token,
-            &Symbol::new(&env, "burn"),
+            &Symbol::new(&env, "minter_burn"),
             burn_args,
         );
 
@@ -623,14 +697,11 @@ impl AmmPool {
     }
 
     /// Quote swap sell (read-only)
-    pub fn quote_swap_sell(env: Env, dob_amount: i128) -> SwapQuote {
+    pub fn quote_swap_sell(env: Env, dob_amount: i128) -> Result<SwapQuote, Error> {
+        let dob_token: Address = env.storage().instance().get(&DataKey::DobToken).unwrap();
         let oracle: Address = env.storage().instance().get(&DataKey::Oracle).unwrap();
 
-        let fair_price: i128 = env.invoke_contract(&oracle, &Symbol::new(&env, "fair_price"), soroban_sdk::vec![&env]);
-        let risk: u32 = env.invoke_contract(&oracle, &Symbol::new(&env, "default_risk"), soroban_sdk::vec![&env]);
-
-        let base_fee_bps = 300 + (risk / 10);
-        let base_fee_bps = if base_fee_bps > 5000 { 5000 } else { base_fee_bps };
+        let (fair_price, base_fee_bps) = Self::redemption_pricing(&env, &oracle, &dob_token)?;
 
         let usdc_needed = (dob_amount * fair_price) / 10_000_000;
         let usdc_after_fee = (usdc_needed * (BPS - base_fee_bps) as i128) / BPS as i128;
@@ -638,23 +709,140 @@ impl AmmPool {
         let usdc_reserve: i128 = env.storage().instance().get(&DataKey::UsdcReserve).unwrap_or(0);
 
         if usdc_reserve >= usdc_after_fee {
-            SwapQuote {
+            Ok(SwapQuote {
                 usdc_out: usdc_after_fee,
                 total_fee_bps: base_fee_bps,
                 from_pool: usdc_after_fee,
                 from_liquid_nodes: 0,
-            }
+            })
         } else {
             let from_pool = usdc_reserve;
             let from_ln = usdc_after_fee - usdc_reserve;
 
-            SwapQuote {
+            Ok(SwapQuote {
                 usdc_out: usdc_after_fee,
                 total_fee_bps: base_fee_bps,
                 from_pool,
                 from_liquid_nodes: from_ln,
+            })
+        }
+    }
+
+    /// Set the oracle TWAP window used to price sells and floor mints (callable by operator)
+    /// A window of 0 sells at the latest spot fair price
+    pub fn set_twap_window(env: Env, window_secs: u64) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        env.storage().instance().set(&DataKey::TwapWindow, &window_secs);
+
+        env.events().publish(
+            (Symbol::new(&env, "twap_window_set"),),
+            window_secs,
+        );
+
+        Ok(())
+    }
+
+    /// Get the oracle TWAP window used to price sells and floor mints
+    pub fn twap_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TwapWindow)
+            .unwrap_or(DEFAULT_TWAP_WINDOW)
+    }
+
+    /// Set the maximum oracle price age accepted for swaps (callable by operator)
+    pub fn set_heartbeat(env: Env, max_age_secs: u64) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        env.storage().instance().set(&DataKey::Heartbeat, &max_age_secs);
+
+        env.events().publish(
+            (Symbol::new(&env, "heartbeat_set"),),
+            max_age_secs,
+        );
+
+        Ok(())
+    }
+
+    /// Get the maximum oracle price age accepted for swaps
+    pub fn heartbeat(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Heartbeat)
+            .unwrap_or(DEFAULT_HEARTBEAT)
+    }
+
+    /// Propose a new operator, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (callable by operator)
+    pub fn propose_operator(env: Env, new_operator: Address, expires_at: Option<u64>) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
             }
         }
+
+        let proposal = OperatorProposal {
+            proposed: new_operator.clone(),
+            expires_at,
+        };
+        env.storage().instance().set(&DataKey::PendingOperator, &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_proposed"), operator),
+            proposal,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending operator proposal (only the proposed address)
+    pub fn accept_operator(env: Env) -> Result<(), Error> {
+        let proposal = Self::pending_operator(env.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
+
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let previous: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        env.storage().instance().set(&DataKey::Operator, &proposal.proposed);
+        env.storage().instance().remove(&DataKey::PendingOperator);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_changed"), previous),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the pending operator proposal (callable by operator)
+    pub fn cancel_proposal(env: Env) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        let proposal = Self::pending_operator(env.clone()).ok_or(Error::NoProposal)?;
+        env.storage().instance().remove(&DataKey::PendingOperator);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_proposal_cancelled"), operator),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Get the pending operator proposal, if any
+    pub fn pending_operator(env: Env) -> Option<OperatorProposal> {
+        env.storage().instance().get(&DataKey::PendingOperator)
     }
 
     /// Get pool reserves
@@ -693,4 +881,615 @@ impl AmmPool {
         let operator = env.storage().instance().get(&DataKey::Operator).unwrap();
         (dob_token, usdc_token, oracle, operator)
     }
+
+    /// Get admin address
+    pub fn admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
+
+    /// Propose a new admin, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (only current admin)
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: Option<u64>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let proposal = AdminProposal {
+            proposed: new_admin.clone(),
+            expires_at,
+        };
+        env.storage().instance().set(&DataKey::PendingAdmin, &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposed"), admin),
+            proposal,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending admin proposal (only the proposed address)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
+
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let previous = Self::admin(env.clone());
+        env.storage().instance().set(&DataKey::Admin, &proposal.proposed);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_changed"), previous),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the pending admin proposal (only current admin)
+    pub fn cancel_admin_proposal(env: Env) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposal_cancelled"), admin),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Get the pending admin proposal, if any
+    pub fn pending_admin(env: Env) -> Option<AdminProposal> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    /// Set the storage TTL thresholds (only admin)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if config.instance_threshold > config.instance_extend_to
+            || config.persistent_threshold > config.persistent_extend_to
+            || config.instance_extend_to > env.storage().max_ttl()
+            || config.persistent_extend_to > env.storage().max_ttl()
+        {
+            return Err(Error::InvalidTtl);
+        }
+
+        env.storage().instance().set(&DataKey::TtlConfig, &config);
+        Self::extend_instance(&env);
+
+        env.events().publish(
+            (Symbol::new(&env, "ttl_config_set"), admin),
+            config,
+        );
+
+        Ok(())
+    }
+
+    /// Get the storage TTL thresholds
+    pub fn ttl_config(env: Env) -> TtlConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TtlConfig)
+            .unwrap_or(TtlConfig {
+                instance_threshold: INSTANCE_LIFETIME_THRESHOLD,
+                instance_extend_to: INSTANCE_BUMP_AMOUNT,
+                persistent_threshold: PERSISTENT_LIFETIME_THRESHOLD,
+                persistent_extend_to: PERSISTENT_BUMP_AMOUNT,
+            })
+    }
+
+    /// Replace the contract code (only admin)
+    /// Call `migrate` afterwards when the new code raises the schema version
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "upgraded"), admin),
+            new_wasm_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Apply storage layout changes up to the current schema version (only admin)
+    /// Contracts initialized before versioning report version 0
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let from_version = Self::schema_version(env.clone());
+        if from_version >= SCHEMA_VERSION {
+            return Err(Error::AlreadyMigrated);
+        }
+
+        // Version 1 introduced no layout change; later steps go here keyed on `from_version`
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+
+        env.events().publish(
+            (Symbol::new(&env, "migrated"), admin),
+            (from_version, SCHEMA_VERSION),
+        );
+
+        Ok(SCHEMA_VERSION)
+    }
+
+    /// Get the storage layout version
+    pub fn schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(0)
+    }
+}
+
+impl AmmPool {
+    fn extend_instance(env: &Env) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .instance()
+            .extend_ttl(config.instance_threshold, config.instance_extend_to);
+    }
+
+    /// Extend an existing persistent entry
+    fn extend_persistent(env: &Env, key: &DataKey) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .persistent()
+            .extend_ttl(key, config.persistent_threshold, config.persistent_extend_to);
+    }
+
+    /// Read the oracle fair price, failing if it is older than the heartbeat
+    /// or the oracle is frozen
+    fn checked_fair_price(env: &Env, oracle: &Address, dob_token: &Address) -> Result<i128, Error> {
+        let heartbeat = Self::heartbeat(env.clone());
+        Ok(dob_oracle_client::fair_price_checked(env, oracle, dob_token, heartbeat)?)
+    }
+
+    /// Price to mint at: the higher of the checked spot price and the TWAP
+    fn mint_price(env: &Env, oracle: &Address, dob_token: &Address) -> Result<i128, Error> {
+        let spot = Self::checked_fair_price(env, oracle, dob_token)?;
+
+        let twap_window = Self::twap_window(env.clone());
+        let twap: i128 = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "twap"),
+            (dob_token.clone(), twap_window).into_val(env),
+        );
+
+        Ok(spot.max(twap))
+    }
+
+    /// Recovery price the oracle settles an impaired series at, failing if
+    /// the oracle is frozen
+    fn recovery_price(env: &Env, oracle: &Address, dob_token: &Address) -> Result<Option<i128>, Error> {
+        Ok(dob_oracle_client::recovery_redemption_price(env, oracle, dob_token)?)
+    }
+
+    /// Fail with `Error::IssuanceClosed` once the oracle reports the series
+    /// Defaulted, Recovered or Matured
+    fn ensure_issuance_open(env: &Env, oracle: &Address, dob_token: &Address) -> Result<(), Error> {
+        let open: bool = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "issuance_open"),
+            (dob_token.clone(),).into_val(env),
+        );
+        if !open {
+            return Err(Error::IssuanceClosed);
+        }
+        Ok(())
+    }
+
+    /// Price and penalty to redeem at: the oracle recovery price without a
+    /// penalty once the series is impaired, otherwise the TWAP less the
+    /// penalty from the oracle's governance-controlled curve
+    fn redemption_pricing(env: &Env, oracle: &Address, dob_token: &Address) -> Result<(i128, u32), Error> {
+        // An impaired series settles at its recovery price, which the heartbeat
+        // does not apply to since the fair price is no longer pushed
+        if let Some(recovery_price) = Self::recovery_price(env, oracle, dob_token)? {
+            return Ok((recovery_price, 0));
+        }
+
+        // Refuse to price against a feed older than the heartbeat
+        Self::checked_fair_price(env, oracle, dob_token)?;
+
+        // Time-weighted price so a single bad push cannot reprice redemptions instantly
+        let twap_window = Self::twap_window(env.clone());
+        let price: i128 = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "twap"),
+            (dob_token.clone(), twap_window).into_val(env),
+        );
+        let risk: u32 = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "default_risk"),
+            (dob_token.clone(),).into_val(env),
+        );
+        let penalty_bps: u32 = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "calculate_penalty"),
+            (dob_token.clone(), risk).into_val(env),
+        );
+
+        Ok((price, penalty_bps))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use dob_token::{DobToken, DobTokenClient};
+    use soroban_sdk::{
+        contract, contracterror, contractimpl, symbol_short,
+        testutils::{Address as _, Ledger},
+        token::StellarAssetClient,
+        String,
+    };
+
+    /// Oracle stand-in with a settable price, TWAP, penalty, age and default
+    /// state, whose feed can also be marked stale or frozen
+    #[contract]
+    pub struct MockOracle;
+
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+    #[repr(u32)]
+    pub enum OracleError {
+        Stale = 6,
+        Paused = 21,
+    }
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_stale(env: Env, stale: bool) {
+            env.storage().instance().set(&symbol_short!("stale"), &stale);
+        }
+
+        pub fn set_paused(env: Env, paused: bool) {
+            env.storage().instance().set(&symbol_short!("paused"), &paused);
+        }
+
+        pub fn set_price(env: Env, price: i128) {
+            env.storage().instance().set(&symbol_short!("price"), &price);
+        }
+
+        pub fn set_twap(env: Env, twap: i128) {
+            env.storage().instance().set(&symbol_short!("twap"), &twap);
+        }
+
+        pub fn set_penalty(env: Env, penalty_bps: u32) {
+            env.storage().instance().set(&symbol_short!("penalty"), &penalty_bps);
+        }
+
+        /// Mark the series Defaulted with the price redemptions settle at
+        pub fn set_defaulted(env: Env, recovery_price: i128) {
+            env.storage().instance().set(&symbol_short!("recovery"), &recovery_price);
+        }
+
+        pub fn set_age(env: Env, age: u64) {
+            env.storage().instance().set(&symbol_short!("age"), &age);
+        }
+
+        pub fn fair_price_checked(env: Env, _asset: Address, max_age: u64) -> Result<i128, OracleError> {
+            if Self::flag(&env, symbol_short!("paused")) {
+                return Err(OracleError::Paused);
+            }
+            let age: u64 = env.storage().instance().get(&symbol_short!("age")).unwrap_or(0);
+            if Self::flag(&env, symbol_short!("stale")) || age > max_age {
+                return Err(OracleError::Stale);
+            }
+            Ok(Self::price(&env))
+        }
+
+        pub fn twap(env: Env, _asset: Address, _window: u64) -> i128 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("twap"))
+                .unwrap_or(Self::price(&env))
+        }
+
+        pub fn default_risk(_env: Env, _asset: Address) -> u32 {
+            1000
+        }
+
+        pub fn calculate_penalty(env: Env, _asset: Address, _risk: u32) -> u32 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("penalty"))
+                .unwrap_or(400)
+        }
+
+        pub fn issuance_open(env: Env, _asset: Address) -> bool {
+            !env.storage().instance().has(&symbol_short!("recovery"))
+        }
+
+        pub fn recovery_redemption_price(env: Env, _asset: Address) -> Result<Option<i128>, OracleError> {
+            if Self::flag(&env, symbol_short!("paused")) {
+                return Err(OracleError::Paused);
+            }
+            Ok(env.storage().instance().get(&symbol_short!("recovery")))
+        }
+    }
+
+    impl MockOracle {
+        fn flag(env: &Env, name: Symbol) -> bool {
+            env.storage().instance().get(&name).unwrap_or(false)
+        }
+
+        fn price(env: &Env) -> i128 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("price"))
+                .unwrap_or(10_000_000)
+        }
+    }
+
+    fn setup(env: &Env) -> (AmmPoolClient<'_>, MockOracleClient<'_>) {
+        let oracle = env.register_contract(None, MockOracle);
+        let usdc_token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        let contract_id = env.register_contract(None, AmmPool);
+        let client = AmmPoolClient::new(env, &contract_id);
+        let dob_token = env.register_contract(None, DobToken);
+
+        env.mock_all_auths();
+        // The pool is the DOB token's first minter
+        DobTokenClient::new(env, &dob_token).initialize(
+            &Address::generate(env),
+            &contract_id,
+            &String::from_str(env, "DOB Token"),
+            &String::from_str(env, "DOB"),
+            &7,
+            &false,
+        );
+        client.initialize(
+            &Address::generate(env),
+            &dob_token,
+            &usdc_token,
+            &oracle,
+            &Address::generate(env),
+        );
+
+        (client, MockOracleClient::new(env, &oracle))
+    }
+
+    #[test]
+    fn test_stale_oracle_blocks_swaps() {
+        let env = Env::default();
+        let (client, oracle) = setup(&env);
+        let trader = Address::generate(&env);
+
+        // 1000 DOB at 1.00 less the 4% penalty, all routed to liquid nodes
+        let quote = client.quote_swap_sell(&1000_0000000);
+        assert_eq!(quote.usdc_out, 960_0000000);
+        assert_eq!(quote.from_liquid_nodes, 960_0000000);
+
+        oracle.set_stale(&true);
+        assert_eq!(client.try_quote_swap_sell(&1000_0000000), Err(Ok(Error::StalePrice)));
+        assert_eq!(client.try_swap_buy(&trader, &100_0000000), Err(Ok(Error::StalePrice)));
+        assert_eq!(client.try_swap_sell(&trader, &1000_0000000), Err(Ok(Error::StalePrice)));
+    }
+
+    #[test]
+    fn test_paused_oracle_blocks_swaps() {
+        let env = Env::default();
+        let (client, oracle) = setup(&env);
+        let trader = Address::generate(&env);
+
+        oracle.set_paused(&true);
+        assert_eq!(client.try_quote_swap_sell(&1000_0000000), Err(Ok(Error::OraclePaused)));
+        assert_eq!(client.try_swap_buy(&trader, &100_0000000), Err(Ok(Error::OraclePaused)));
+        assert_eq!(client.try_swap_sell(&trader, &1000_0000000), Err(Ok(Error::OraclePaused)));
+    }
+
+    #[test]
+    fn test_heartbeat_setter() {
+        let env = Env::default();
+        let (client, oracle) = setup(&env);
+        let (_, usdc_token, _, operator) = client.get_addresses();
+        let trader = Address::generate(&env);
+        StellarAssetClient::new(&env, &usdc_token).mint(&trader, &100_0000000);
+
+        // A price two days old is past the default one-day heartbeat
+        assert_eq!(client.heartbeat(), 86_400);
+        oracle.set_age(&(2 * 86_400));
+        assert_eq!(client.try_swap_buy(&trader, &100_0000000), Err(Ok(Error::StalePrice)));
+
+        client.set_heartbeat(&(3 * 86_400));
+        assert_eq!(env.auths()[0].0, operator);
+        assert_eq!(client.heartbeat(), 3 * 86_400);
+        assert!(client.swap_buy(&trader, &100_0000000) > 0);
+
+        // Tightening it again rejects the same price
+        client.set_heartbeat(&3_600);
+        assert_eq!(client.try_quote_swap_sell(&100_0000000), Err(Ok(Error::StalePrice)));
+    }
+
+    #[test]
+    fn test_swaps_at_live_price() {
+        let env = Env::default();
+        let (client, oracle) = setup(&env);
+        let (dob_token, usdc_token, _, operator) = client.get_addresses();
+        let dob = token::Client::new(&env, &dob_token);
+        let usdc = token::Client::new(&env, &usdc_token);
+        let trader = Address::generate(&env);
+        let provider = Address::generate(&env);
+        StellarAssetClient::new(&env, &usdc_token).mint(&trader, &1000_0000000);
+        StellarAssetClient::new(&env, &usdc_token).mint(&provider, &1000_0000000);
+
+        // 1000 USDC less the 1% DEX fee, 99% of the rest to the operator: 980.1 USDC at 1.25
+        oracle.set_price(&12_500_000);
+        let minted = client.swap_buy(&trader, &1000_0000000);
+        assert_eq!(minted, 784_0800000);
+        assert_eq!(dob.balance(&trader), minted);
+        assert_eq!(usdc.balance(&operator), 980_1000000);
+
+        DobTokenClient::new(&env, &dob_token).mint(&client.address, &provider, &1000_0000000);
+        client.add_liquidity(&provider, &1000_0000000, &1000_0000000);
+
+        // 500 DOB at 0.80 less the 4% penalty, paid from pool reserves
+        oracle.set_price(&8_000_000);
+        assert_eq!(client.swap_sell(&trader, &500_0000000), 384_0000000);
+        assert_eq!(usdc.balance(&trader), 384_0000000);
+        assert_eq!(dob.balance(&trader), minted - 500_0000000);
+    }
+
+    #[test]
+    fn test_mint_at_max_of_spot_and_twap() {
+        let env = Env::default();
+        let (client, oracle) = setup(&env);
+        let (_, usdc_token, _, _) = client.get_addresses();
+        let trader = Address::generate(&env);
+        StellarAssetClient::new(&env, &usdc_token).mint(&trader, &2000_0000000);
+
+        // 980.1 USDC reaches the operator per 1000 spent
+        oracle.set_price(&10_000_000);
+
+        // A TWAP below spot does not cheapen mints
+        oracle.set_twap(&8_000_000);
+        assert_eq!(client.swap_buy(&trader, &1000_0000000), 980_1000000);
+
+        // A TWAP above spot, e.g. right after a low push, prices the mint
+        oracle.set_twap(&12_500_000);
+        assert_eq!(client.swap_buy(&trader, &1000_0000000), 784_0800000);
+    }
+
+    #[test]
+    fn test_sell_uses_oracle_penalty() {
+        let env = Env::default();
+        let (client, oracle) = setup(&env);
+        let (dob_token, usdc_token, _, _) = client.get_addresses();
+        let seller = Address::generate(&env);
+        let provider = Address::generate(&env);
+        let dob_client = DobTokenClient::new(&env, &dob_token);
+        StellarAssetClient::new(&env, &usdc_token).mint(&provider, &1000_0000000);
+        dob_client.mint(&client.address, &provider, &1000_0000000);
+        dob_client.mint(&client.address, &seller, &500_0000000);
+        client.add_liquidity(&provider, &1000_0000000, &1000_0000000);
+
+        // The haircut follows the oracle's curve rather than a fixed fee
+        oracle.set_penalty(&1500);
+        assert_eq!(client.quote_swap_sell(&500_0000000).usdc_out, 425_0000000);
+        assert_eq!(client.swap_sell(&seller, &500_0000000), 425_0000000);
+        assert_eq!(token::Client::new(&env, &usdc_token).balance(&seller), 425_0000000);
+    }
+
+    #[test]
+    fn test_defaulted_series() {
+        let env = Env::default();
+        let (client, oracle) = setup(&env);
+        let (dob_token, usdc_token, _, _) = client.get_addresses();
+        let trader = Address::generate(&env);
+        let provider = Address::generate(&env);
+        let dob_client = DobTokenClient::new(&env, &dob_token);
+        StellarAssetClient::new(&env, &usdc_token).mint(&trader, &100_0000000);
+        StellarAssetClient::new(&env, &usdc_token).mint(&provider, &1000_0000000);
+        dob_client.mint(&client.address, &provider, &1000_0000000);
+        dob_client.mint(&client.address, &trader, &500_0000000);
+        client.add_liquidity(&provider, &1000_0000000, &1000_0000000);
+
+        oracle.set_defaulted(&4_000_000);
+        assert_eq!(client.try_swap_buy(&trader, &100_0000000), Err(Ok(Error::IssuanceClosed)));
+
+        // Sells settle at the recovery price without the risk penalty, even
+        // though the fair price is no longer pushed
+        oracle.set_stale(&true);
+        assert_eq!(client.quote_swap_sell(&500_0000000).usdc_out, 200_0000000);
+        assert_eq!(client.swap_sell(&trader, &500_0000000), 200_0000000);
+        assert_eq!(token::Client::new(&env, &usdc_token).balance(&trader), 300_0000000);
+    }
+
+    #[test]
+    fn test_operator_handover() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        let operator = client.get_addresses().3;
+        let new_operator = Address::generate(&env);
+
+        // Proposals must expire in the future and lapse once that passes
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        assert_eq!(
+            client.try_propose_operator(&new_operator, &Some(1_000)),
+            Err(Ok(Error::ProposalExpired))
+        );
+        client.propose_operator(&new_operator, &Some(2_000));
+        env.ledger().with_mut(|li| li.timestamp = 2_001);
+        assert_eq!(client.try_accept_operator(), Err(Ok(Error::ProposalExpired)));
+
+        client.cancel_proposal();
+        assert_eq!(env.auths()[0].0, operator);
+        assert_eq!(client.pending_operator(), None);
+        assert_eq!(client.try_accept_operator(), Err(Ok(Error::NoProposal)));
+        assert_eq!(client.try_cancel_proposal(), Err(Ok(Error::NoProposal)));
+
+        // The role only moves once the proposed address accepts
+        client.propose_operator(&new_operator, &None);
+        assert_eq!(client.get_addresses().3, operator);
+        client.accept_operator();
+        assert_eq!(env.auths()[0].0, new_operator);
+        assert_eq!(client.get_addresses().3, new_operator);
+        assert_eq!(client.pending_operator(), None);
+
+        client.set_heartbeat(&600);
+        assert_eq!(env.auths()[0].0, new_operator);
+    }
+
+    #[test]
+    fn test_admin_handover() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        let admin = client.admin();
+        let new_admin = Address::generate(&env);
+
+        // Upgrade-level settings answer to the admin, not the operator
+        client.set_ttl_config(&client.ttl_config());
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(client.try_migrate(), Err(Ok(Error::AlreadyMigrated)));
+
+        // A lapsed proposal cannot be accepted
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        assert_eq!(
+            client.try_propose_admin(&new_admin, &Some(1_000)),
+            Err(Ok(Error::ProposalExpired))
+        );
+        client.propose_admin(&new_admin, &Some(2_000));
+        env.ledger().with_mut(|li| li.timestamp = 2_001);
+        assert_eq!(client.try_accept_admin(), Err(Ok(Error::ProposalExpired)));
+
+        client.cancel_admin_proposal();
+        assert_eq!(client.pending_admin(), None);
+        assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoProposal)));
+
+        client.propose_admin(&new_admin, &None);
+        assert_eq!(client.admin(), admin);
+        client.accept_admin();
+        assert_eq!(env.auths()[0].0, new_admin);
+        assert_eq!(client.admin(), new_admin);
+        assert_eq!(client.pending_admin(), None);
+    }
 }
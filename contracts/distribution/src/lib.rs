@@ -0,0 +1,1174 @@
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, contracterror, token, Address, BytesN, Env, Symbol, Vec,
+};
+
+/// Storage keys for the distribution contract
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    DobToken,         // DOB token whose holders receive revenue
+    UsdcToken,        // USDC token revenue is paid in
+    Operator,         // Operator depositing period revenue
+    Admin,            // Admin upgrading the contract, separate from the operator receiving sweeps
+    ClaimWindow,      // Epoch length, and how long revenue stays claimable after an epoch closes
+    CurrentEpoch,     // Id of the epoch taking deposits, unset before the first deposit
+    Epoch(u32),       // EpochRecord of an epoch that has not been swept
+    SweptEpochs,      // Epochs before this id have been swept
+    Excluded,         // Vec<Address> of reserve holders left out of distributions
+    Holder(Address),  // HolderState of a DOB holder
+    TotalDeposited,   // Total USDC deposited
+    TotalClaimed,     // Total USDC claimed by holders
+    PendingOperator,  // OperatorProposal awaiting acceptance
+    PendingAdmin,     // AdminProposal awaiting acceptance
+    TtlConfig,        // Storage TTL thresholds, defaults when unset
+    SchemaVersion,    // Storage layout version applied by migrate()
+}
+
+/// Revenue deposited during one epoch, claimable until the epoch expires
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpochRecord {
+    pub acc_reward_per_token: i128, // USDC per eligible DOB token, scaled by REWARD_PRECISION
+    pub amount: i128,               // USDC deposited in the epoch
+    pub claimed: i128,              // USDC claimed from the epoch so far
+    pub closes_at: u64,             // Deposits from this timestamp open the next epoch
+    pub expires_at: u64,            // Unclaimed revenue can be swept from this timestamp
+}
+
+/// Per-holder settlement state
+/// An epoch only closes once the one before it has expired, so a holder is
+/// never owed revenue from more than the current and the previous epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HolderState {
+    pub epoch: u32,             // Epoch the holder was last settled in
+    pub reward_debt: i128,      // Accumulator of `epoch` at the last settlement
+    pub accrued: i128,          // Settled revenue from `epoch` not yet claimed
+    pub previous_accrued: i128, // Settled revenue from the epoch before `epoch` not yet claimed
+}
+
+/// Revenue deposit event
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RevenueDepositedEvent {
+    pub epoch: u32,
+    pub amount: i128,
+    pub eligible_supply: i128,
+    pub expires_at: u64,
+}
+
+/// Pending operator handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// Ledgers before expiry at which entries are extended, and the TTL they are
+/// extended to; persistent values apply to epoch and holder entries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    pub instance_threshold: u32,
+    pub instance_extend_to: u32,
+    pub persistent_threshold: u32,
+    pub persistent_extend_to: u32,
+}
+
+/// Pending admin handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// Errors that can be returned by the contract
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    InvalidAmount = 1,
+    NoSupply = 2,
+    NothingToClaim = 3,
+    ClaimWindowOpen = 4,
+    NoProposal = 5,
+    ProposalExpired = 6,
+    AlreadyMigrated = 7,
+    TooManyExcluded = 8,
+    InvalidTtl = 9,
+}
+
+const REWARD_PRECISION: i128 = 1_000_000_000_000_000_000; // 1e18
+const DAY_IN_LEDGERS: u32 = 17280; // ~5s per ledger
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const SCHEMA_VERSION: u32 = 1; // Bump together with a migrate() step
+const MAX_EXCLUDED: u32 = 10;
+
+/// DobDistribution - pays period revenue to DOB holders pro rata
+///
+/// Each deposit adds amount / eligible supply to the current epoch's
+/// reward-per-token accumulator, and DobToken calls `update_account` before
+/// every balance change so holders are settled at the balance they actually
+/// held, at a constant cost per update. Reserve holders (the AMM pool, primary
+/// market and the token's redemption escrow) are excluded from the eligible supply.
+/// Epochs last one claim window and their unclaimed revenue can be swept once
+/// a further claim window has passed
+#[contract]
+pub struct DobDistribution;
+
+#[contractimpl]
+impl DobDistribution {
+    /// Initialize the distribution contract
+    /// The admin upgrades the contract and should be a different key than the
+    /// operator, who deposits revenue and receives sweeps
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        dob_token: Address,
+        usdc_token: Address,
+        operator: Address,
+        claim_window_secs: u64,
+    ) {
+        if env.storage().instance().has(&DataKey::DobToken) {
+            panic!("Already initialized");
+        }
+
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::DobToken, &dob_token);
+        env.storage().instance().set(&DataKey::UsdcToken, &usdc_token);
+        env.storage().instance().set(&DataKey::Operator, &operator);
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        env.storage()
+            .instance()
+            .set(&DataKey::ClaimWindow, &claim_window_secs);
+    }
+
+    /// Deposit period revenue for current DOB holders (only operator)
+    /// Excluded reserve holders take no share
+    /// Returns the epoch the revenue was added to
+    pub fn deposit(env: Env, amount: i128) -> Result<u32, Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        Self::extend_instance(&env);
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let dob_token: Address = env.storage().instance().get(&DataKey::DobToken).unwrap();
+        let dob_client = token::Client::new(&env, &dob_token);
+        let dob_supply: i128 = env.invoke_contract(
+            &dob_token,
+            &Symbol::new(&env, "total_supply"),
+            soroban_sdk::vec![&env],
+        );
+        let mut eligible_supply = dob_supply;
+        for account in Self::excluded(env.clone()).iter() {
+            eligible_supply -= dob_client.balance(&account);
+        }
+        if eligible_supply <= 0 {
+            return Err(Error::NoSupply);
+        }
+
+        let usdc_token: Address = env.storage().instance().get(&DataKey::UsdcToken).unwrap();
+        token::Client::new(&env, &usdc_token).transfer(
+            &operator,
+            env.current_contract_address(),
+            &amount,
+        );
+
+        let now = env.ledger().timestamp();
+        let current = Self::current_epoch(env.clone());
+        let (epoch, mut record) = match current.and_then(|e| Self::epoch(env.clone(), e)) {
+            Some(record) if now < record.closes_at => (current.unwrap(), record),
+            previous => {
+                // The new epoch stays open until the previous one has expired,
+                // so holders are only ever owed revenue from two epochs
+                let window = Self::claim_window(env.clone());
+                let closes_at = previous
+                    .map_or(now + window, |record| (now + window).max(record.expires_at));
+                let record = EpochRecord {
+                    acc_reward_per_token: 0,
+                    amount: 0,
+                    claimed: 0,
+                    closes_at,
+                    expires_at: closes_at + window,
+                };
+                (current.map_or(0, |epoch| epoch + 1), record)
+            }
+        };
+
+        record.acc_reward_per_token += (amount * REWARD_PRECISION) / eligible_supply;
+        record.amount += amount;
+        Self::save_epoch(&env, epoch, &record);
+        env.storage().instance().set(&DataKey::CurrentEpoch, &epoch);
+
+        let total_deposited = Self::total_deposited(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalDeposited, &(total_deposited + amount));
+
+        env.events().publish(
+            (Symbol::new(&env, "revenue_deposited"),),
+            RevenueDepositedEvent {
+                epoch,
+                amount,
+                eligible_supply,
+                expires_at: record.expires_at,
+            },
+        );
+
+        Ok(epoch)
+    }
+
+    /// Settle a holder at the balance held up to now (only DOB token)
+    /// Called by DobToken before it changes the holder's balance; the balance
+    /// is passed in so the token is not re-entered
+    pub fn update_account(env: Env, account: Address, balance: i128) {
+        let dob_token: Address = env.storage().instance().get(&DataKey::DobToken).unwrap();
+        dob_token.require_auth();
+
+        Self::extend_instance(&env);
+
+        let state = Self::settled_state(&env, &account, balance);
+        Self::save_holder(&env, &account, &state);
+    }
+
+    /// Claim all unswept revenue settled to a holder
+    pub fn claim(env: Env, holder: Address) -> Result<i128, Error> {
+        holder.require_auth();
+
+        Self::extend_instance(&env);
+
+        let balance = Self::dob_balance(&env, &holder);
+        let mut state = Self::settled_state(&env, &holder, balance);
+
+        let mut amount = 0;
+        if state.epoch > 0 {
+            amount += Self::claim_from_epoch(&env, state.epoch - 1, state.previous_accrued);
+        }
+        amount += Self::claim_from_epoch(&env, state.epoch, state.accrued);
+
+        if amount <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+
+        // Revenue from expired epochs is left to the sweep
+        state.accrued = 0;
+        state.previous_accrued = 0;
+        Self::save_holder(&env, &holder, &state);
+
+        let total_claimed = Self::total_claimed(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalClaimed, &(total_claimed + amount));
+
+        let usdc_token: Address = env.storage().instance().get(&DataKey::UsdcToken).unwrap();
+        token::Client::new(&env, &usdc_token).transfer(
+            &env.current_contract_address(),
+            &holder,
+            &amount,
+        );
+
+        env.events()
+            .publish((Symbol::new(&env, "revenue_claimed"), holder), amount);
+
+        Ok(amount)
+    }
+
+    /// Revenue a holder could claim now
+    pub fn claimable(env: Env, holder: Address) -> i128 {
+        let balance = Self::dob_balance(&env, &holder);
+        let state = Self::settled_state(&env, &holder, balance);
+
+        let mut amount = 0;
+        if state.epoch > 0 && Self::is_claimable(&env, state.epoch - 1) {
+            amount += state.previous_accrued;
+        }
+        if Self::is_claimable(&env, state.epoch) {
+            amount += state.accrued;
+        }
+        amount
+    }
+
+    /// Send the unclaimed remainder of every expired epoch to the operator (only operator)
+    /// Epochs are swept oldest first, stopping at the first one still claimable;
+    /// holders keep their revenue from later epochs
+    pub fn sweep(env: Env) -> Result<i128, Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        Self::extend_instance(&env);
+
+        let now = env.ledger().timestamp();
+        let end = Self::current_epoch(env.clone()).map_or(0, |epoch| epoch + 1);
+        let first = Self::swept_epochs(env.clone());
+
+        let mut next = first;
+        let mut unclaimed = 0i128;
+        while next < end {
+            let key = DataKey::Epoch(next);
+            let record: EpochRecord = env.storage().persistent().get(&key).unwrap();
+            if now < record.expires_at {
+                break;
+            }
+            unclaimed += record.amount - record.claimed;
+            env.storage().persistent().remove(&key);
+            next += 1;
+        }
+
+        if next == first {
+            return Err(Error::ClaimWindowOpen);
+        }
+
+        env.storage().instance().set(&DataKey::SweptEpochs, &next);
+
+        if unclaimed > 0 {
+            let usdc_token: Address = env.storage().instance().get(&DataKey::UsdcToken).unwrap();
+            token::Client::new(&env, &usdc_token).transfer(
+                &env.current_contract_address(),
+                &operator,
+                &unclaimed,
+            );
+        }
+
+        env.events()
+            .publish((Symbol::new(&env, "revenue_swept"), first, next), unclaimed);
+
+        Ok(unclaimed)
+    }
+
+    /// Set the claim window in seconds for future epochs (only operator)
+    pub fn set_claim_window(env: Env, claim_window_secs: u64) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ClaimWindow, &claim_window_secs);
+
+        env.events()
+            .publish((Symbol::new(&env, "claim_window_set"),), claim_window_secs);
+
+        Ok(())
+    }
+
+    /// Get the claim window in seconds
+    pub fn claim_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ClaimWindow)
+            .unwrap_or(0)
+    }
+
+    /// Exclude a reserve holder such as the AMM pool, the primary market or
+    /// the DOB token's redemption escrow from distributions, or include it
+    /// again (only operator)
+    /// The account is settled first and only earns from deposits made while included
+    pub fn set_excluded(env: Env, account: Address, excluded: bool) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        let balance = Self::dob_balance(&env, &account);
+        let state = Self::settled_state(&env, &account, balance);
+        Self::save_holder(&env, &account, &state);
+
+        let mut list = Self::excluded(env.clone());
+        let position = list.first_index_of(&account);
+        match (excluded, position) {
+            (true, None) => {
+                if list.len() >= MAX_EXCLUDED {
+                    return Err(Error::TooManyExcluded);
+                }
+                list.push_back(account.clone());
+            }
+            (false, Some(index)) => {
+                list.remove(index);
+            }
+            _ => return Ok(()),
+        }
+        env.storage().instance().set(&DataKey::Excluded, &list);
+
+        env.events()
+            .publish((Symbol::new(&env, "holder_excluded"), account), excluded);
+
+        Ok(())
+    }
+
+    /// Get the reserve holders excluded from distributions
+    pub fn excluded(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Excluded)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the epoch taking deposits, if any deposit was made
+    pub fn current_epoch(env: Env) -> Option<u32> {
+        env.storage().instance().get(&DataKey::CurrentEpoch)
+    }
+
+    /// Get an epoch that has not been swept yet
+    pub fn epoch(env: Env, epoch: u32) -> Option<EpochRecord> {
+        env.storage().persistent().get(&DataKey::Epoch(epoch))
+    }
+
+    /// Get the number of epochs swept so far; their revenue can no longer be claimed
+    pub fn swept_epochs(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SweptEpochs)
+            .unwrap_or(0)
+    }
+
+    /// Get total USDC deposited
+    pub fn total_deposited(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalDeposited)
+            .unwrap_or(0)
+    }
+
+    /// Get total USDC claimed
+    pub fn total_claimed(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalClaimed)
+            .unwrap_or(0)
+    }
+
+    /// Get contract addresses
+    pub fn get_addresses(env: Env) -> (Address, Address, Address) {
+        let dob_token = env.storage().instance().get(&DataKey::DobToken).unwrap();
+        let usdc_token = env.storage().instance().get(&DataKey::UsdcToken).unwrap();
+        let operator = env.storage().instance().get(&DataKey::Operator).unwrap();
+        (dob_token, usdc_token, operator)
+    }
+
+    /// Propose a new operator, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (operator only)
+    pub fn propose_operator(env: Env, new_operator: Address, expires_at: Option<u64>) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let proposal = OperatorProposal {
+            proposed: new_operator.clone(),
+            expires_at,
+        };
+        env.storage().instance().set(&DataKey::PendingOperator, &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_proposed"), operator),
+            proposal,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending operator proposal (only the proposed address)
+    pub fn accept_operator(env: Env) -> Result<(), Error> {
+        let proposal = Self::pending_operator(env.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
+
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let previous: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        env.storage().instance().set(&DataKey::Operator, &proposal.proposed);
+        env.storage().instance().remove(&DataKey::PendingOperator);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_changed"), previous),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the pending operator proposal (operator only)
+    pub fn cancel_proposal(env: Env) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        let proposal = Self::pending_operator(env.clone()).ok_or(Error::NoProposal)?;
+        env.storage().instance().remove(&DataKey::PendingOperator);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_proposal_cancelled"), operator),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Get the pending operator proposal, if any
+    pub fn pending_operator(env: Env) -> Option<OperatorProposal> {
+        env.storage().instance().get(&DataKey::PendingOperator)
+    }
+
+    /// Get admin address
+    pub fn admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
+
+    /// Propose a new admin, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (only current admin)
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: Option<u64>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let proposal = AdminProposal {
+            proposed: new_admin.clone(),
+            expires_at,
+        };
+        env.storage().instance().set(&DataKey::PendingAdmin, &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposed"), admin),
+            proposal,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending admin proposal (only the proposed address)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
+
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let previous = Self::admin(env.clone());
+        env.storage().instance().set(&DataKey::Admin, &proposal.proposed);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_changed"), previous),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the pending admin proposal (only current admin)
+    pub fn cancel_admin_proposal(env: Env) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposal_cancelled"), admin),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Get the pending admin proposal, if any
+    pub fn pending_admin(env: Env) -> Option<AdminProposal> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    /// Set the storage TTL thresholds (only admin)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if config.instance_threshold > config.instance_extend_to
+            || config.persistent_threshold > config.persistent_extend_to
+            || config.instance_extend_to > env.storage().max_ttl()
+            || config.persistent_extend_to > env.storage().max_ttl()
+        {
+            return Err(Error::InvalidTtl);
+        }
+
+        env.storage().instance().set(&DataKey::TtlConfig, &config);
+        Self::extend_instance(&env);
+
+        env.events().publish(
+            (Symbol::new(&env, "ttl_config_set"), admin),
+            config,
+        );
+
+        Ok(())
+    }
+
+    /// Get the storage TTL thresholds
+    pub fn ttl_config(env: Env) -> TtlConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TtlConfig)
+            .unwrap_or(TtlConfig {
+                instance_threshold: INSTANCE_LIFETIME_THRESHOLD,
+                instance_extend_to: INSTANCE_BUMP_AMOUNT,
+                persistent_threshold: PERSISTENT_LIFETIME_THRESHOLD,
+                persistent_extend_to: PERSISTENT_BUMP_AMOUNT,
+            })
+    }
+
+    /// Replace the contract code (only admin)
+    /// Call `migrate` afterwards when the new code raises the schema version
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "upgraded"), admin),
+            new_wasm_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Apply storage layout changes up to the current schema version (only admin)
+    /// Contracts initialized before versioning report version 0
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let from_version = Self::schema_version(env.clone());
+        if from_version >= SCHEMA_VERSION {
+            return Err(Error::AlreadyMigrated);
+        }
+
+        // Version 1 introduced no layout change; later steps go here keyed on `from_version`
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+
+        env.events().publish(
+            (Symbol::new(&env, "migrated"), admin),
+            (from_version, SCHEMA_VERSION),
+        );
+
+        Ok(SCHEMA_VERSION)
+    }
+
+    /// Get the storage layout version
+    pub fn schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(0)
+    }
+}
+
+impl DobDistribution {
+    fn extend_instance(env: &Env) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .instance()
+            .extend_ttl(config.instance_threshold, config.instance_extend_to);
+    }
+
+    /// Extend an existing persistent entry
+    fn extend_persistent(env: &Env, key: &DataKey) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .persistent()
+            .extend_ttl(key, config.persistent_threshold, config.persistent_extend_to);
+    }
+
+    fn dob_balance(env: &Env, holder: &Address) -> i128 {
+        let dob_token: Address = env.storage().instance().get(&DataKey::DobToken).unwrap();
+        token::Client::new(env, &dob_token).balance(holder)
+    }
+
+    fn save_holder(env: &Env, holder: &Address, state: &HolderState) {
+        let key = DataKey::Holder(holder.clone());
+        env.storage().persistent().set(&key, state);
+        Self::extend_persistent(env, &key);
+    }
+
+    fn save_epoch(env: &Env, epoch: u32, record: &EpochRecord) {
+        let key = DataKey::Epoch(epoch);
+        env.storage().persistent().set(&key, record);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Accumulator of an epoch, zero once it has been swept
+    fn epoch_acc(env: &Env, epoch: u32) -> i128 {
+        Self::epoch(env.clone(), epoch).map_or(0, |record| record.acc_reward_per_token)
+    }
+
+    /// Whether an epoch's revenue can still be claimed
+    fn is_claimable(env: &Env, epoch: u32) -> bool {
+        Self::epoch(env.clone(), epoch)
+            .is_some_and(|record| env.ledger().timestamp() < record.expires_at)
+    }
+
+    /// Record `amount` as claimed from an epoch if it is still claimable,
+    /// returning what was claimed
+    fn claim_from_epoch(env: &Env, epoch: u32, amount: i128) -> i128 {
+        if amount <= 0 || !Self::is_claimable(env, epoch) {
+            return 0;
+        }
+
+        let mut record = Self::epoch(env.clone(), epoch).unwrap();
+        record.claimed += amount;
+        Self::save_epoch(env, epoch, &record);
+        amount
+    }
+
+    /// Holder state settled at `balance` up to now, reading at most two epochs;
+    /// excluded holders earn nothing
+    fn settled_state(env: &Env, holder: &Address, balance: i128) -> HolderState {
+        let state: HolderState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Holder(holder.clone()))
+            .unwrap_or(HolderState {
+                epoch: 0,
+                reward_debt: 0,
+                accrued: 0,
+                previous_accrued: 0,
+            });
+
+        let Some(current) = Self::current_epoch(env.clone()) else {
+            return state;
+        };
+
+        let balance = if Self::excluded(env.clone()).contains(holder) {
+            0
+        } else {
+            balance
+        };
+        let earned = |acc: i128, debt: i128| (balance * (acc - debt)) / REWARD_PRECISION;
+        let current_acc = Self::epoch_acc(env, current);
+
+        if state.epoch == current {
+            return HolderState {
+                epoch: current,
+                reward_debt: current_acc,
+                accrued: state.accrued + earned(current_acc, state.reward_debt),
+                previous_accrued: state.previous_accrued,
+            };
+        }
+
+        // The balance was held unchanged since the last settlement, so the
+        // previous epoch is earned from where that settlement left off, or in
+        // full if it came earlier; anything older has expired
+        let previous_acc = Self::epoch_acc(env, current - 1);
+        let previous_accrued = if state.epoch + 1 == current {
+            state.accrued + earned(previous_acc, state.reward_debt)
+        } else {
+            earned(previous_acc, 0)
+        };
+
+        HolderState {
+            epoch: current,
+            reward_debt: current_acc,
+            accrued: earned(current_acc, 0),
+            previous_accrued,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{storage::Persistent as _, Address as _, Ledger};
+    use dob_token::{DobToken, DobTokenClient};
+    use soroban_sdk::{token::StellarAssetClient, IntoVal, String};
+
+    /// Minimal DOB token that notifies the distributor like DobToken does
+    #[contract]
+    pub struct MockDobToken;
+
+    #[contractimpl]
+    impl MockDobToken {
+        pub fn set_distributor(env: Env, distributor: Address) {
+            env.storage().instance().set(&Symbol::new(&env, "dist"), &distributor);
+        }
+
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            Self::write(&env, &to, Self::balance(env.clone(), to.clone()) + amount);
+            let supply = Self::total_supply(env.clone()) + amount;
+            env.storage().instance().set(&Symbol::new(&env, "supply"), &supply);
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            Self::write(&env, &from, Self::balance(env.clone(), from.clone()) - amount);
+            Self::write(&env, &to, Self::balance(env.clone(), to.clone()) + amount);
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().persistent().get(&id).unwrap_or(0)
+        }
+
+        pub fn total_supply(env: Env) -> i128 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "supply"))
+                .unwrap_or(0)
+        }
+
+        fn write(env: &Env, account: &Address, amount: i128) {
+            let distributor: Option<Address> = env.storage().instance().get(&Symbol::new(env, "dist"));
+            if let Some(distributor) = distributor {
+                let old_balance = Self::balance(env.clone(), account.clone());
+                let _: () = env.invoke_contract(
+                    &distributor,
+                    &Symbol::new(env, "update_account"),
+                    (account.clone(), old_balance).into_val(env),
+                );
+            }
+            env.storage().persistent().set(account, &amount);
+        }
+    }
+
+    #[test]
+    fn test_pro_rata_distribution() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let usdc_client = token::Client::new(&env, &usdc_id);
+        StellarAssetClient::new(&env, &usdc_id).mint(&operator, &1000_0000000);
+
+        let dob_id = env.register_contract(None, MockDobToken);
+        let dob_client = MockDobTokenClient::new(&env, &dob_id);
+        let distribution_id = env.register_contract(None, DobDistribution);
+        let client = DobDistributionClient::new(&env, &distribution_id);
+
+        client.initialize(&admin, &dob_id, &usdc_id, &operator, &(30 * 86_400));
+        dob_client.set_distributor(&distribution_id);
+
+        assert_eq!(client.try_deposit(&100_0000000), Err(Ok(Error::NoSupply)));
+
+        // Alice holds 75%, Bob 25% during the first period
+        dob_client.mint(&alice, &750_0000000);
+        dob_client.mint(&bob, &250_0000000);
+        client.deposit(&100_0000000);
+        assert_eq!(client.claimable(&alice), 75_0000000);
+        assert_eq!(client.claimable(&bob), 25_0000000);
+
+        // Alice moves half of her tokens to Bob: earlier revenue stays with her
+        dob_client.transfer(&alice, &bob, &375_0000000);
+        client.deposit(&100_0000000);
+        assert_eq!(client.claimable(&alice), 75_0000000 + 37_5000000);
+        assert_eq!(client.claimable(&bob), 25_0000000 + 62_5000000);
+
+        assert_eq!(client.claim(&alice), 112_5000000);
+        assert_eq!(usdc_client.balance(&alice), 112_5000000);
+        assert_eq!(client.try_claim(&alice), Err(Ok(Error::NothingToClaim)));
+        assert_eq!(client.total_claimed(), 112_5000000);
+    }
+
+    #[test]
+    fn test_sweep_after_claim_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let usdc_client = token::Client::new(&env, &usdc_id);
+        StellarAssetClient::new(&env, &usdc_id).mint(&operator, &1000_0000000);
+
+        let dob_id = env.register_contract(None, MockDobToken);
+        let dob_client = MockDobTokenClient::new(&env, &dob_id);
+        let distribution_id = env.register_contract(None, DobDistribution);
+        let client = DobDistributionClient::new(&env, &distribution_id);
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.initialize(&admin, &dob_id, &usdc_id, &operator, &86_400);
+        dob_client.set_distributor(&distribution_id);
+
+        dob_client.mint(&alice, &500_0000000);
+        dob_client.mint(&bob, &500_0000000);
+        client.deposit(&100_0000000);
+        client.claim(&alice);
+
+        assert_eq!(client.try_sweep(), Err(Ok(Error::ClaimWindowOpen)));
+
+        // A deposit once the first epoch has closed opens the next one
+        env.ledger().with_mut(|li| li.timestamp = 1_000 + 86_400);
+        assert_eq!(client.deposit(&100_0000000), 1);
+        assert_eq!(client.current_epoch(), Some(1));
+        assert_eq!(client.try_sweep(), Err(Ok(Error::ClaimWindowOpen)));
+
+        // Bob never claimed from the first epoch; only that share returns to the operator
+        env.ledger().with_mut(|li| li.timestamp = 1_000 + 2 * 86_400);
+        assert_eq!(client.sweep(), 50_0000000);
+        assert_eq!(usdc_client.balance(&operator), 850_0000000);
+        assert_eq!(client.swept_epochs(), 1);
+        assert_eq!(client.epoch(&0), None);
+        assert_eq!(client.try_sweep(), Err(Ok(Error::ClaimWindowOpen)));
+
+        // Revenue from the open epoch survives the sweep
+        assert_eq!(client.claimable(&alice), 50_0000000);
+        assert_eq!(client.claimable(&bob), 50_0000000);
+        assert_eq!(client.claim(&bob), 50_0000000);
+
+        // Once the second epoch expires, only Alice's unclaimed share is swept
+        env.ledger().with_mut(|li| li.timestamp = 1_000 + 3 * 86_400);
+        assert_eq!(client.sweep(), 50_0000000);
+        assert_eq!(client.claimable(&alice), 0);
+        assert_eq!(usdc_client.balance(&distribution_id), 0);
+    }
+
+    #[test]
+    fn test_idle_holder_across_epochs() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        StellarAssetClient::new(&env, &usdc_id).mint(&operator, &1000_0000000);
+
+        let dob_id = env.register_contract(None, MockDobToken);
+        let dob_client = MockDobTokenClient::new(&env, &dob_id);
+        let distribution_id = env.register_contract(None, DobDistribution);
+        let client = DobDistributionClient::new(&env, &distribution_id);
+
+        env.ledger().with_mut(|li| li.timestamp = 0);
+        client.initialize(&admin, &dob_id, &usdc_id, &operator, &86_400);
+        dob_client.set_distributor(&distribution_id);
+
+        dob_client.mint(&alice, &100_0000000);
+
+        // Several deposits in one epoch accumulate into a single record
+        client.deposit(&10_0000000);
+        client.deposit(&10_0000000);
+        let record = client.epoch(&0).unwrap();
+        assert_eq!(record.amount, 20_0000000);
+        assert_eq!(record.expires_at, 2 * 86_400);
+
+        // One deposit per epoch while Alice stays idle
+        for epoch in 1..5u32 {
+            env.ledger().with_mut(|li| li.timestamp = epoch as u64 * 86_400);
+            assert_eq!(client.deposit(&20_0000000), epoch);
+        }
+
+        // Only the current and previous epochs are still claimable
+        assert_eq!(client.claimable(&alice), 40_0000000);
+
+        // Settling on a transfer reads two epochs regardless of how long she was idle
+        dob_client.transfer(&alice, &bob, &50_0000000);
+        assert_eq!(client.claimable(&alice), 40_0000000);
+        assert_eq!(client.claimable(&bob), 0);
+
+        // The previous epoch expires before the current one
+        env.ledger().with_mut(|li| li.timestamp = 5 * 86_400);
+        assert_eq!(client.claimable(&alice), 20_0000000);
+        assert_eq!(client.claim(&alice), 20_0000000);
+        assert_eq!(client.sweep(), 80_0000000);
+        assert_eq!(client.swept_epochs(), 4);
+    }
+
+    #[test]
+    fn test_excluded_reserves() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let pool = Address::generate(&env);
+
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        StellarAssetClient::new(&env, &usdc_id).mint(&operator, &1000_0000000);
+
+        let dob_id = env.register_contract(None, MockDobToken);
+        let dob_client = MockDobTokenClient::new(&env, &dob_id);
+        let distribution_id = env.register_contract(None, DobDistribution);
+        let client = DobDistributionClient::new(&env, &distribution_id);
+
+        client.initialize(&admin, &dob_id, &usdc_id, &operator, &86_400);
+        dob_client.set_distributor(&distribution_id);
+
+        // The pool's reserve would otherwise take 60% of every deposit
+        dob_client.mint(&alice, &400_0000000);
+        dob_client.mint(&pool, &600_0000000);
+        client.set_excluded(&pool, &true);
+        assert_eq!(client.excluded(), soroban_sdk::vec![&env, pool.clone()]);
+
+        client.deposit(&100_0000000);
+        assert_eq!(client.claimable(&alice), 100_0000000);
+        assert_eq!(client.claimable(&pool), 0);
+
+        // Only excluded holders: nothing is eligible
+        dob_client.transfer(&alice, &pool, &400_0000000);
+        assert_eq!(client.try_deposit(&100_0000000), Err(Ok(Error::NoSupply)));
+
+        // Included again, the pool earns from later deposits only
+        client.set_excluded(&pool, &false);
+        assert_eq!(client.claimable(&pool), 0);
+        client.deposit(&100_0000000);
+        assert_eq!(client.claimable(&pool), 100_0000000);
+        assert_eq!(client.claimable(&alice), 100_0000000);
+    }
+
+    #[test]
+    fn test_dob_token_settles_holders() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let minter = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        StellarAssetClient::new(&env, &usdc_id).mint(&operator, &1000_0000000);
+
+        let dob_id = env.register_contract(None, DobToken);
+        let dob_client = DobTokenClient::new(&env, &dob_id);
+        dob_client.initialize(
+            &admin,
+            &minter,
+            &String::from_str(&env, "DOB Solar"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+        dob_client.set_redemption_desk(&operator, &usdc_id);
+
+        let distribution_id = env.register_contract(None, DobDistribution);
+        let client = DobDistributionClient::new(&env, &distribution_id);
+        client.initialize(&admin, &dob_id, &usdc_id, &operator, &86_400);
+        dob_client.set_distributor(&Some(distribution_id.clone()));
+
+        dob_client.mint(&minter, &alice, &800_0000000);
+        dob_client.mint(&minter, &bob, &200_0000000);
+
+        // Tokens escrowed for redemption sit on the token contract, which is excluded
+        client.set_excluded(&dob_id, &true);
+        dob_client.request_redemption(&bob, &200_0000000, &190_0000000);
+        client.deposit(&100_0000000);
+        assert_eq!(client.claimable(&alice), 100_0000000);
+        assert_eq!(client.claimable(&bob), 0);
+
+        // The token settles both sides of a transfer at their old balances
+        dob_client.transfer(&alice, &bob, &400_0000000);
+        client.deposit(&100_0000000);
+        assert_eq!(client.claimable(&alice), 150_0000000);
+        assert_eq!(client.claimable(&bob), 50_0000000);
+
+        assert_eq!(client.claim(&alice), 150_0000000);
+        assert_eq!(client.claim(&bob), 50_0000000);
+    }
+
+    #[test]
+    fn test_ttl_config() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let alice = Address::generate(&env);
+
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let dob_id = env.register_contract(None, MockDobToken);
+        let dob_client = MockDobTokenClient::new(&env, &dob_id);
+        let distribution_id = env.register_contract(None, DobDistribution);
+        let client = DobDistributionClient::new(&env, &distribution_id);
+
+        client.initialize(&admin, &dob_id, &usdc_id, &operator, &86_400);
+        dob_client.set_distributor(&distribution_id);
+
+        let config = TtlConfig {
+            instance_threshold: 10_000,
+            instance_extend_to: 20_000,
+            persistent_threshold: 10_000,
+            persistent_extend_to: 20_000,
+        };
+        client.set_ttl_config(&config);
+        assert_eq!(env.auths()[0].0, admin);
+        dob_client.mint(&alice, &100_0000000);
+
+        let holder_ttl = || {
+            env.as_contract(&distribution_id, || {
+                env.storage().persistent().get_ttl(&DataKey::Holder(alice.clone()))
+            })
+        };
+        assert_eq!(holder_ttl(), 20_000);
+
+        let invalid = TtlConfig {
+            persistent_threshold: 30_000,
+            ..config
+        };
+        assert_eq!(client.try_set_ttl_config(&invalid), Err(Ok(Error::InvalidTtl)));
+    }
+
+    #[test]
+    fn test_admin_handover() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let new_admin = Address::generate(&env);
+
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let dob_id = env.register_contract(None, MockDobToken);
+        let distribution_id = env.register_contract(None, DobDistribution);
+        let client = DobDistributionClient::new(&env, &distribution_id);
+
+        client.initialize(&admin, &dob_id, &usdc_id, &operator, &86_400);
+        assert_eq!(client.admin(), admin);
+
+        // Upgrade-level settings answer to the admin, not the sweeping operator
+        client.set_ttl_config(&client.ttl_config());
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(client.try_migrate(), Err(Ok(Error::AlreadyMigrated)));
+
+        client.propose_admin(&new_admin, &None);
+        assert_eq!(client.admin(), admin);
+        client.accept_admin();
+        assert_eq!(env.auths()[0].0, new_admin);
+        assert_eq!(client.admin(), new_admin);
+        assert_eq!(client.pending_admin(), None);
+        assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoProposal)));
+    }
+}
@@ -1,14 +1,217 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, contracterror, Address, Env, Symbol};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, contracterror, xdr::ToXdr, Address, Bytes, BytesN, Env,
+    IntoVal, Symbol, TryFromVal, Val, Vec,
+};
 
 /// Storage keys for the oracle contract
+/// Per-series entries are keyed by the series' DOB token address
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
-    FairPrice,    // Fair price per DOB token (7 decimals: 1.00 = 10000000)
-    DefaultRisk,  // Default risk in basis points (10000 = 100%)
-    Updater,      // Address authorized to update values
+    Series(Address),  // SeriesState: fair price, default risk, updater and last update
+    SeriesList,       // Vec<Address> of registered bond series
+    Cashflows(Address),    // Vec<Cashflow> projected cashflow schedule
+    DiscountRate(Address), // Discount rate per period in basis points
+    HistoryHead(Address),  // Ring buffer slot of the newest observation
+    HistoryLen(Address),   // Number of filled ring buffer slots
+    Observation(Address, u32), // PriceObservation stored in a ring buffer slot
+    Admin,        // Manages reporters and oracle configuration
+    PendingAdmin, // AdminProposal awaiting acceptance
+    Reporters,    // Vec<Address> of addresses allowed to submit round reports
+    Quorum,       // Reports needed to finalize a round
+    Round(Address),             // Current reporting round id
+    RoundReports(Address, u32), // Vec<Report> submitted for a round
+    RoundOpenedAt(Address),     // Timestamp of the first report in the current round
+    RoundTimeout,      // Seconds a round may collect reports before it expires
+    OutlierThreshold,  // Deviation from the median (bps) that flags a reporter
+    Signers,      // Vec<BytesN<32>> ed25519 keys accepted for signed reports
+    SignerNonce(BytesN<32>),      // Last nonce accepted from a signer
+    LastSignedTimestamp(Address), // Timestamp of the last accepted signed report
+    MaxReportAge,     // Seconds a signed report may age before it is rejected
+    DeviationLimits,  // DeviationLimits applied to single-source updates
+    PendingTimelock,  // Seconds before a pending update can be executed by anyone
+    PendingUpdate(Address),  // PendingUpdate held back by the circuit breaker
+    PenaltyCurve(Address),   // Vec<PenaltyPoint> mapping default risk to redemption penalty
+    BaseAsset,        // SEP-40 quote asset prices are denominated in
+    Guardian,         // Address allowed to freeze the oracle
+    Frozen,           // bool, reads fail with Error::Paused while set
+    PendingUpdater(Address), // UpdaterProposal awaiting acceptance
+    CreditStatus(Address),   // CreditStatus of the series
+    CreditHistory(Address),  // Vec<CreditStatus> of past transitions, oldest first
+    RecoveryPrice(Address),  // Price redemptions settle at once Defaulted (7 decimals)
+    Realized(Address, u32),  // RealizedCashflow reported for a schedule period
+    Performance(Address),    // CashflowPerformance running totals
+    UnderperformancePolicy,  // Optional UnderperformancePolicy feeding default risk
+    SettlementPrice(Address), // Final price matured tokens redeem at (7 decimals)
+    Amortizer(Address),      // Contract allowed to scale down the cashflow schedule
+    TtlConfig,        // Storage TTL thresholds, defaults when unset
+    SchemaVersion,    // Storage layout version applied by migrate()
+}
+
+/// SEP-40 asset identifier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// SEP-40 price record
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Projected cashflow for a single period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cashflow {
+    pub period: u32,  // Periods from valuation date (1 = next period)
+    pub amount: i128, // Expected USDC revenue (7 decimals)
+}
+
+/// Breakdown of an on-chain fair price derivation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Valuation {
+    pub discounted_value: i128,   // Sum of discounted cashflows before risk haircut
+    pub present_value: i128,      // Discounted value × survival probability
+    pub outstanding_supply: i128, // DOB total supply of the series (7 decimals)
+    pub discount_rate_bps: u32,
+    pub default_risk: u32,
+    pub fair_price: i128,         // Present value per DOB token (7 decimals)
+}
+
+/// Timestamped oracle value kept in the price history ring buffer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceObservation {
+    pub timestamp: u64,
+    pub fair_price: i128,
+    pub default_risk: u32,
+}
+
+/// When the latest fair price and risk were published
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceTimestamp {
+    pub timestamp: u64,
+    pub ledger: u32,
+}
+
+/// A reporter's submission for a round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report {
+    pub reporter: Address,
+    pub fair_price: i128,
+    pub default_risk: u32,
+}
+
+/// Published result of a reporting round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundResult {
+    pub round: u32,
+    pub fair_price: i128,   // Median of reported fair prices
+    pub default_risk: u32,  // Median of reported risks
+    pub report_count: u32,
+}
+
+/// Reporter outlier event data
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutlierReport {
+    pub round: u32,
+    pub fair_price: i128,
+    pub default_risk: u32,
+    pub median_price: i128,
+    pub median_risk: u32,
+}
+
+/// Off-chain valuation payload signed with a registered ed25519 key
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedReport {
+    pub asset: Address, // DOB token of the series being priced
+    pub fair_price: i128,
+    pub default_risk: u32,
+    pub timestamp: u64, // When the valuation was produced (must not be in the future)
+    pub nonce: u64,     // Strictly increasing per signer
+}
+
+/// Maximum change a single update may apply before it needs confirmation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeviationLimits {
+    pub max_price_deviation_bps: u32, // Relative to the current fair price
+    pub max_risk_deviation_bps: u32,  // Absolute change in default risk
+}
+
+/// Update held back by the circuit breaker
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingUpdate {
+    pub fair_price: i128,
+    pub default_risk: u32,
+    pub proposed_at: u64,
+    pub executable_at: u64, // Anyone may execute once the timelock has passed
+    pub observed_at: PriceTimestamp, // When the values were produced; becomes the last update
+    pub settles: bool,      // Also fixes the series' settlement price once published
+}
+
+/// Breakpoint of the piecewise-linear redemption penalty curve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PenaltyPoint {
+    pub risk_bps: u32,
+    pub penalty_bps: u32,
+}
+
+/// Price state of a bond series served by this oracle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeriesState {
+    pub fair_price: i128,
+    pub default_risk: u32,
+    pub updater: Address,
+    pub last_updated: PriceTimestamp,
+}
+
+/// Revenue actually received for a schedule period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RealizedCashflow {
+    pub period: u32,
+    pub projected: i128,             // Scheduled amount for the period (7 decimals)
+    pub amount: i128,                // Realized USDC revenue (7 decimals)
+    pub evidence_hash: BytesN<32>,   // Hash of the off-chain revenue evidence
+    pub reported_at: u64,
+}
+
+/// Realized vs projected performance across all reported periods
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CashflowPerformance {
+    pub periods_reported: u32,
+    pub projected_total: i128,
+    pub realized_total: i128,
+    pub variance: i128,              // realized_total - projected_total
+    pub variance_bps: i128,          // variance relative to projected_total
+    pub consecutive_shortfalls: u32, // Run of short periods ending at the latest reported period
+}
+
+/// Escalate default risk after persistent underperformance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnderperformancePolicy {
+    pub shortfall_threshold_bps: u32, // A period counts as short below projected × (1 - threshold)
+    pub consecutive_periods: u32,     // Short periods in a row before risk is raised
+    pub risk_step_bps: u32,           // Added to default risk for each further short period
 }
 
 /// Oracle update event data
@@ -19,36 +222,148 @@ pub struct OracleUpdate {
     pub default_risk: u32,
 }
 
+/// Credit lifecycle of a revenue bond
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CreditState {
+    Performing,
+    Watchlist,
+    Delinquent,
+    Defaulted,
+    Recovered,
+    Matured,
+}
+
+/// Credit state with the reason code and timestamp of the transition into it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditStatus {
+    pub state: CreditState,
+    pub reason_code: u32,
+    pub since: u64,
+}
+
+/// Pending updater handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpdaterProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// Pending admin handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// Ledgers before expiry at which entries are extended, and the TTL they are
+/// extended to; persistent values apply to per-series entries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    pub instance_threshold: u32,
+    pub instance_extend_to: u32,
+    pub persistent_threshold: u32,
+    pub persistent_extend_to: u32,
+}
+
 /// Errors that can be returned by the oracle
 #[contracterror]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Error {
     Unauthorized = 1,
+    InvalidSchedule = 2,
+    NotConfigured = 3,
+    NoSupply = 4,
+    NoHistory = 5,
+    Stale = 6,
+    NotReporter = 7,
+    AlreadyReporter = 8,
+    AlreadyReported = 9,
+    InvalidQuorum = 10,
+    InvalidReport = 11,
+    UnknownSigner = 12,
+    AlreadySigner = 13,
+    ReplayedNonce = 14,
+    InvalidTimestamp = 15,
+    NoPendingUpdate = 16,
+    TimelockActive = 17,
+    InvalidPenaltyCurve = 18,
+    AssetNotFound = 19,
+    AssetExists = 20,
+    Paused = 21,
+    NoProposal = 22,
+    ProposalExpired = 23,
+    InvalidTransition = 24,
+    UnknownPeriod = 25,
+    AlreadyMigrated = 26,
+    ReportersActive = 27,
+    AlreadySettled = 28,
+    InvalidTtl = 29,
 }
 
+// Constants
+const BPS: u32 = 10000; // Basis points denominator
+const PRICE_SCALE: i128 = 10_000_000; // 7 decimals
+const MAX_CASHFLOWS: u32 = 120; // Bounds the PV loop (e.g. 10 years of monthly periods)
+const MAX_CASHFLOW_PERIOD: u32 = 480; // Bounds the discount factor steps (40 years of monthly periods)
+const HISTORY_CAPACITY: u32 = 48; // Observations kept in the ring buffer
+const DEFAULT_OUTLIER_THRESHOLD: u32 = 1000; // 10% away from the median
+const DEFAULT_PENDING_TIMELOCK: u64 = 86_400; // 1 day before anyone can execute
+const DEFAULT_ROUND_TIMEOUT: u64 = 3600; // Unfinished rounds expire after 1 hour
+const DEFAULT_MAX_REPORT_AGE: u64 = 3600; // Signed reports older than 1 hour are rejected
+const MAX_PENALTY_BPS: u32 = 5000; // Penalty can never exceed 50%
+const MAX_PENALTY_POINTS: u32 = 16;
+const PRICE_DECIMALS: u32 = 7;
+const RESOLUTION_SECS: u32 = 3600; // Expected update cadence reported to SEP-40 readers
+const DAY_IN_LEDGERS: u32 = 17280; // ~5s per ledger
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const SCHEMA_VERSION: u32 = 1; // Bump together with a migrate() step
+
 /// DobOracle - Simple push oracle for fair price and default risk
 /// Perfect for testing and MVP - trusted operator updates values
+/// Every bond series is keyed by its DOB token address
 #[contract]
 pub struct DobOracle;
 
 #[contractimpl]
 impl DobOracle {
-    /// Initialize the oracle contract
-    pub fn initialize(env: Env, updater: Address, initial_fair_price: i128, initial_risk: u32) {
-        if env.storage().instance().has(&DataKey::Updater) {
+    /// Initialize the oracle contract with its first bond series
+    /// The admin manages configuration and should be a different key than the updater
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        updater: Address,
+        initial_fair_price: i128,
+        initial_risk: u32,
+    ) {
+        if env.storage().instance().has(&DataKey::Admin) {
             panic!("Already initialized");
         }
 
-        updater.require_auth();
+        admin.require_auth();
 
-        env.storage().instance().set(&DataKey::Updater, &updater);
-        env.storage().instance().set(&DataKey::FairPrice, &initial_fair_price);
-        env.storage()
-            .instance()
-            .set(&DataKey::DefaultRisk, &initial_risk);
+        if initial_fair_price <= 0 {
+            panic!("Invalid fair price");
+        }
+
+        if initial_risk > 10000 {
+            panic!("Risk cannot exceed 100%");
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        Self::add_series(&env, &asset, &updater, initial_fair_price, initial_risk);
 
         env.events().publish(
-            (Symbol::new(&env, "initialized"),),
+            (Symbol::new(&env, "initialized"), asset),
             OracleUpdate {
                 fair_price: initial_fair_price,
                 default_risk: initial_risk,
@@ -56,34 +371,46 @@ impl DobOracle {
         );
     }
 
-    /// Get current fair price per DOB token
+    /// Get current fair price per DOB token of a series
     /// Returns value with 7 decimals (e.g., 10000000 = 1.00 USDC per token)
-    pub fn fair_price(env: Env) -> i128 {
-        env.storage()
-            .instance()
-            .get(&DataKey::FairPrice)
-            .unwrap_or(10_000_000) // Default: 1.00 with 7 decimals
+    pub fn fair_price(env: Env, asset: Address) -> Result<i128, Error> {
+        Self::require_live(&env)?;
+        Ok(Self::series_state(&env, &asset)?.fair_price)
     }
 
-    /// Get current default risk in basis points
+    /// Get current default risk of a series in basis points
     /// 10000 basis points = 100%
     /// 1000 basis points = 10%
-    pub fn default_risk(env: Env) -> u32 {
-        env.storage()
-            .instance()
-            .get(&DataKey::DefaultRisk)
-            .unwrap_or(1000) // Default: 10%
+    pub fn default_risk(env: Env, asset: Address) -> Result<u32, Error> {
+        Self::require_live(&env)?;
+        Ok(Self::series_state(&env, &asset)?.default_risk)
     }
 
-    /// Update fair price and default risk (only updater can call)
-    pub fn update(env: Env, new_fair_price: i128, new_default_risk: u32) -> Result<(), Error> {
-        let updater: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Updater)
-            .expect("Updater not set");
+    /// Get timestamp and ledger of the latest published fair price and risk
+    pub fn last_updated(env: Env, asset: Address) -> Result<PriceTimestamp, Error> {
+        Ok(Self::series_state(&env, &asset)?.last_updated)
+    }
+
+    /// Get current fair price, failing with `Error::Stale` if it was
+    /// published more than `max_age` seconds ago
+    pub fn fair_price_checked(env: Env, asset: Address, max_age: u64) -> Result<i128, Error> {
+        Self::require_live(&env)?;
 
-        updater.require_auth();
+        let state = Self::series_state(&env, &asset)?;
+        let age = env.ledger().timestamp().saturating_sub(state.last_updated.timestamp);
+
+        if age > max_age {
+            return Err(Error::Stale);
+        }
+
+        Ok(state.fair_price)
+    }
+
+    /// Update fair price and default risk of a series (only its updater)
+    /// Disabled while a reporter set exists, so prices come from quorum rounds
+    pub fn update(env: Env, asset: Address, new_fair_price: i128, new_default_risk: u32) -> Result<(), Error> {
+        Self::require_updater(&env, &asset)?;
+        Self::require_no_reporters(&env)?;
 
         if new_fair_price <= 0 {
             panic!("Invalid fair price");
@@ -93,182 +420,3273 @@ impl DobOracle {
             panic!("Risk cannot exceed 100%");
         }
 
-        env.storage().instance().set(&DataKey::FairPrice, &new_fair_price);
-        env.storage()
-            .instance()
-            .set(&DataKey::DefaultRisk, &new_default_risk);
+        Self::publish_or_hold(
+            &env,
+            &asset,
+            new_fair_price,
+            new_default_risk,
+            Self::now(&env),
+            false,
+        );
+
+        Ok(())
+    }
+
+    /// Fair price in effect at a given ledger timestamp
+    /// Fails if the timestamp predates the retained history
+    pub fn price_at(env: Env, asset: Address, timestamp: u64) -> Result<i128, Error> {
+        Self::require_live(&env)?;
+
+        let len = Self::history_len(&env, &asset);
+        for back in 0..len {
+            let observation = Self::observation(&env, &asset, back);
+            if observation.timestamp <= timestamp {
+                return Ok(observation.fair_price);
+            }
+        }
+
+        Err(Error::NoHistory)
+    }
+
+    /// Time-weighted average fair price over the last `window_secs` seconds
+    /// Each observation is weighted by how long it was the live price; if the
+    /// history is shorter than the window, the covered span is averaged
+    pub fn twap(env: Env, asset: Address, window_secs: u64) -> Result<i128, Error> {
+        Self::require_live(&env)?;
+
+        let len = Self::history_len(&env, &asset);
+        if len == 0 {
+            return Err(Error::NoHistory);
+        }
+
+        let now = env.ledger().timestamp();
+        let window_start = now.saturating_sub(window_secs);
+
+        let mut segment_end = now;
+        let mut weighted_sum = 0i128;
+        let mut covered_secs = 0i128;
+
+        for back in 0..len {
+            let observation = Self::observation(&env, &asset, back);
+            let segment_start = observation.timestamp.max(window_start);
+
+            if segment_end > segment_start {
+                let duration = (segment_end - segment_start) as i128;
+                weighted_sum += observation.fair_price * duration;
+                covered_secs += duration;
+            }
+
+            if observation.timestamp <= window_start {
+                break;
+            }
+            segment_end = observation.timestamp;
+        }
+
+        if covered_secs == 0 {
+            // Zero window or every observation landed this ledger: use the latest
+            return Ok(Self::observation(&env, &asset, 0).fair_price);
+        }
+
+        Ok(weighted_sum / covered_secs)
+    }
+
+    /// Most recent observations, newest first (at most `limit`)
+    pub fn price_history(env: Env, asset: Address, limit: u32) -> Result<Vec<PriceObservation>, Error> {
+        Self::require_live(&env)?;
+        Ok(Self::recent_observations(&env, &asset, limit))
+    }
+
+    /// Get the updater of a series
+    pub fn updater(env: Env, asset: Address) -> Result<Address, Error> {
+        Ok(Self::series_state(&env, &asset)?.updater)
+    }
+
+    /// Propose a new updater for a series, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (only current updater)
+    pub fn propose_updater(
+        env: Env,
+        asset: Address,
+        new_updater: Address,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        let updater = Self::require_updater(&env, &asset)?.updater;
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let proposal = UpdaterProposal {
+            proposed: new_updater.clone(),
+            expires_at,
+        };
+        Self::save(&env, &DataKey::PendingUpdater(asset.clone()), &proposal);
 
         env.events().publish(
-            (Symbol::new(&env, "oracle_updated"),),
-            OracleUpdate {
-                fair_price: new_fair_price,
-                default_risk: new_default_risk,
-            },
+            (Symbol::new(&env, "updater_proposed"), asset, updater),
+            proposal,
         );
 
         Ok(())
     }
 
-    /// Get current updater address
-    pub fn updater(env: Env) -> Address {
+    /// Accept a pending updater proposal (only the proposed address)
+    pub fn accept_updater(env: Env, asset: Address) -> Result<(), Error> {
+        let proposal = Self::pending_updater(env.clone(), asset.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
+
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let mut state = Self::series_state(&env, &asset)?;
+        let previous = state.updater;
+        state.updater = proposal.proposed.clone();
+        Self::save(&env, &DataKey::Series(asset.clone()), &state);
         env.storage()
-            .instance()
-            .get(&DataKey::Updater)
-            .expect("Updater not set")
-    }
+            .persistent()
+            .remove(&DataKey::PendingUpdater(asset.clone()));
 
-    /// Transfer updater role to new address (only current updater)
-    pub fn set_updater(env: Env, new_updater: Address) -> Result<(), Error> {
-        let updater: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Updater)
-            .expect("Updater not set");
+        env.events().publish(
+            (Symbol::new(&env, "updater_changed"), asset, previous),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
 
-        updater.require_auth();
+    /// Withdraw the pending updater proposal of a series (only current updater)
+    pub fn cancel_proposal(env: Env, asset: Address) -> Result<(), Error> {
+        let updater = Self::require_updater(&env, &asset)?.updater;
 
-        env.storage().instance().set(&DataKey::Updater, &new_updater);
+        let proposal = Self::pending_updater(env.clone(), asset.clone()).ok_or(Error::NoProposal)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingUpdater(asset.clone()));
 
         env.events().publish(
-            (Symbol::new(&env, "updater_changed"), updater),
-            new_updater.clone(),
+            (Symbol::new(&env, "updater_proposal_cancelled"), asset, updater),
+            proposal.proposed,
         );
 
         Ok(())
     }
 
-    /// Calculate redemption penalty based on current risk
-    /// Returns penalty in basis points (10000 = 100%)
-    pub fn calculate_penalty(env: Env) -> u32 {
-        let risk = Self::default_risk(env);
+    /// Get the pending updater proposal of a series, if any
+    pub fn pending_updater(env: Env, asset: Address) -> Option<UpdaterProposal> {
+        Self::load(&env, &DataKey::PendingUpdater(asset))
+    }
+
+    /// Set per-update deviation limits for `update` and signed reports (only admin)
+    /// Updates beyond the limits are held as pending instead of published
+    pub fn set_deviation_limits(env: Env, limits: DeviationLimits) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
 
-        // Base penalty 3% + risk factor (risk/10)
-        // e.g., 10% default risk = 300 + 100 = 400 bps = 4% penalty
-        let penalty = 300 + (risk / 10);
+        env.storage().instance().set(&DataKey::DeviationLimits, &limits);
 
-        // Cap penalty at 50% (5000 bps)
-        if penalty > 5000 {
-            5000
-        } else {
-            penalty
-        }
+        env.events()
+            .publish((Symbol::new(&env, "deviation_limits_set"),), limits);
+
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
+    /// Remove deviation limits so every update publishes immediately (only admin)
+    pub fn clear_deviation_limits(env: Env) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
 
-    #[test]
-    fn test_initialize() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, DobOracle);
-        let client = DobOracleClient::new(&env, &contract_id);
+        env.storage().instance().remove(&DataKey::DeviationLimits);
 
-        let updater = Address::generate(&env);
+        env.events()
+            .publish((Symbol::new(&env, "deviation_limits_cleared"),), ());
 
-        env.mock_all_auths();
+        Ok(())
+    }
 
-        // Initialize with 1.00 fair price and 10% risk
-        client.initialize(&updater, &10_000_000, &1000);
+    /// Get the configured deviation limits, if any
+    pub fn deviation_limits(env: Env) -> Option<DeviationLimits> {
+        env.storage().instance().get(&DataKey::DeviationLimits)
+    }
 
-        assert_eq!(client.fair_price(), 10_000_000);
-        assert_eq!(client.default_risk(), 1000);
-        assert_eq!(client.updater(), updater);
+    /// Set how long a pending update waits before anyone can execute it (only admin)
+    pub fn set_pending_timelock(env: Env, timelock_secs: u64) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingTimelock, &timelock_secs);
+
+        Ok(())
     }
 
-    #[test]
-    fn test_update() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, DobOracle);
-        let client = DobOracleClient::new(&env, &contract_id);
+    /// Get the pending update timelock in seconds
+    pub fn pending_timelock(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PendingTimelock)
+            .unwrap_or(DEFAULT_PENDING_TIMELOCK)
+    }
 
-        let updater = Address::generate(&env);
+    /// Get the update of a series held back by the circuit breaker, if any
+    pub fn pending_update(env: Env, asset: Address) -> Option<PendingUpdate> {
+        Self::load(&env, &DataKey::PendingUpdate(asset))
+    }
 
-        env.mock_all_auths();
+    /// Second approver confirms the pending update immediately (only admin)
+    /// The admin must be a different address than the series updater
+    pub fn approve_pending_update(env: Env, asset: Address) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
 
-        client.initialize(&updater, &10_000_000, &1000);
+        if admin == Self::series_state(&env, &asset)?.updater {
+            return Err(Error::Unauthorized);
+        }
 
-        // Update to fair price 1.15 and 7% risk
-        client.update(&11_500_000, &700);
+        let pending = Self::pending_update(env.clone(), asset.clone()).ok_or(Error::NoPendingUpdate)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingUpdate(asset.clone()));
+
+        Self::publish_pending(&env, &asset, &pending);
 
-        assert_eq!(client.fair_price(), 11_500_000);
-        assert_eq!(client.default_risk(), 700);
+        Ok(())
     }
 
-    #[test]
-    fn test_calculate_penalty() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, DobOracle);
-        let client = DobOracleClient::new(&env, &contract_id);
+    /// Publish the pending update once its timelock has passed (callable by anyone)
+    pub fn execute_pending_update(env: Env, asset: Address) -> Result<(), Error> {
+        let pending = Self::pending_update(env.clone(), asset.clone()).ok_or(Error::NoPendingUpdate)?;
 
-        let updater = Address::generate(&env);
+        if env.ledger().timestamp() < pending.executable_at {
+            return Err(Error::TimelockActive);
+        }
 
-        env.mock_all_auths();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingUpdate(asset.clone()));
 
-        // 10% default risk
-        client.initialize(&updater, &10_000_000, &1000);
-        // Penalty = 300 + 1000/10 = 400 bps = 4%
-        assert_eq!(client.calculate_penalty(), 400);
+        Self::publish_pending(&env, &asset, &pending);
 
-        // 35% default risk
-        client.update(&10_000_000, &3500);
-        // Penalty = 300 + 3500/10 = 650 bps = 6.5%
-        assert_eq!(client.calculate_penalty(), 650);
+        Ok(())
+    }
 
-        // Very high risk (60%)
-        client.update(&10_000_000, &6000);
-        // Penalty = 300 + 6000/10 = 900, but capped at 5000 (50%)
-        assert_eq!(client.calculate_penalty(), 900);
+    /// Discard the pending update of a series (only admin)
+    pub fn reject_pending_update(env: Env, asset: Address) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        let pending = Self::pending_update(env.clone(), asset.clone()).ok_or(Error::NoPendingUpdate)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingUpdate(asset.clone()));
+
+        env.events().publish(
+            (Symbol::new(&env, "oracle_update_rejected"), asset),
+            OracleUpdate {
+                fair_price: pending.fair_price,
+                default_risk: pending.default_risk,
+            },
+        );
+
+        Ok(())
     }
 
-    #[test]
-    fn test_set_updater() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, DobOracle);
-        let client = DobOracleClient::new(&env, &contract_id);
+    /// Get admin address
+    pub fn admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
 
-        let updater1 = Address::generate(&env);
-        let updater2 = Address::generate(&env);
+    /// Propose a new admin, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (only current admin)
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: Option<u64>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
 
-        env.mock_all_auths();
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
+            }
+        }
 
-        client.initialize(&updater1, &10_000_000, &1000);
-        assert_eq!(client.updater(), updater1);
+        let proposal = AdminProposal {
+            proposed: new_admin,
+            expires_at,
+        };
+        env.storage().instance().set(&DataKey::PendingAdmin, &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposed"), admin),
+            proposal,
+        );
 
-        // Transfer updater role
-        client.set_updater(&updater2);
-        assert_eq!(client.updater(), updater2);
+        Ok(())
     }
 
-    #[test]
-    #[should_panic(expected = "Invalid NAV")]
-    fn test_invalid_nav() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, DobOracle);
-        let client = DobOracleClient::new(&env, &contract_id);
+    /// Accept a pending admin proposal (only the proposed address)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
 
-        let updater = Address::generate(&env);
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
 
-        env.mock_all_auths();
+        let previous = Self::admin(env.clone());
+        env.storage().instance().set(&DataKey::Admin, &proposal.proposed);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_changed"), previous),
+            proposal.proposed,
+        );
 
-        client.initialize(&updater, &10_000_000, &1000);
-        client.update(&0, &1000); // Should panic
+        Ok(())
     }
 
-    #[test]
-    #[should_panic(expected = "Risk cannot exceed 100%")]
-    fn test_invalid_risk() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, DobOracle);
-        let client = DobOracleClient::new(&env, &contract_id);
+    /// Withdraw the pending admin proposal (only current admin)
+    pub fn cancel_admin_proposal(env: Env) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
 
-        let updater = Address::generate(&env);
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        env.storage().instance().remove(&DataKey::PendingAdmin);
 
-        env.mock_all_auths();
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposal_cancelled"), admin),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Get the pending admin proposal, if any
+    pub fn pending_admin(env: Env) -> Option<AdminProposal> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    /// Set the guardian allowed to freeze the oracle (only admin)
+    pub fn set_guardian(env: Env, guardian: Address) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+
+        env.events()
+            .publish((Symbol::new(&env, "guardian_set"),), guardian);
+
+        Ok(())
+    }
+
+    /// Get the guardian, if one is set
+    pub fn guardian(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Guardian)
+    }
+
+    /// Emergency freeze: every price read fails with `Error::Paused` until
+    /// the admin unfreezes (guardian or admin)
+    pub fn freeze(env: Env, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+
+        if caller != Self::admin(env.clone()) && Self::guardian(env.clone()) != Some(caller.clone()) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::Frozen, &true);
+
+        env.events()
+            .publish((Symbol::new(&env, "oracle_frozen"),), caller);
+
+        Ok(())
+    }
+
+    /// Resume serving prices (only admin)
+    pub fn unfreeze(env: Env) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Frozen, &false);
+
+        env.events()
+            .publish((Symbol::new(&env, "oracle_unfrozen"),), admin);
+
+        Ok(())
+    }
+
+    /// Whether the oracle is frozen
+    pub fn is_frozen(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Frozen)
+            .unwrap_or(false)
+    }
+
+    /// Add an address to the reporter set (only admin)
+    pub fn add_reporter(env: Env, reporter: Address) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        let mut reporters = Self::reporters(env.clone());
+        if reporters.contains(&reporter) {
+            return Err(Error::AlreadyReporter);
+        }
+
+        reporters.push_back(reporter.clone());
+        env.storage().instance().set(&DataKey::Reporters, &reporters);
+
+        env.events()
+            .publish((Symbol::new(&env, "reporter_added"),), reporter);
+
+        Ok(())
+    }
+
+    /// Remove an address from the reporter set (only admin)
+    /// Fails if the remaining reporters could no longer reach quorum, so the
+    /// last reporter can only be dropped through `clear_reporters`
+    pub fn remove_reporter(env: Env, reporter: Address) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        let mut reporters = Self::reporters(env.clone());
+        let index = reporters
+            .first_index_of(&reporter)
+            .ok_or(Error::NotReporter)?;
+
+        if reporters.len() - 1 < Self::quorum(env.clone()) {
+            return Err(Error::InvalidQuorum);
+        }
+
+        reporters.remove(index);
+        env.storage().instance().set(&DataKey::Reporters, &reporters);
+
+        env.events()
+            .publish((Symbol::new(&env, "reporter_removed"),), reporter);
+
+        Ok(())
+    }
+
+    /// Disband the reporter set and reset the quorum (only admin)
+    /// Hands pricing back to each series' updater and signed reports
+    pub fn clear_reporters(env: Env) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        let reporters = Self::reporters(env.clone());
+        env.storage().instance().remove(&DataKey::Reporters);
+        env.storage().instance().remove(&DataKey::Quorum);
+
+        env.events()
+            .publish((Symbol::new(&env, "reporters_cleared"),), reporters.len());
+
+        Ok(())
+    }
+
+    /// Set how many reports finalize a round (only admin)
+    pub fn set_quorum(env: Env, quorum: u32) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        if quorum == 0 || quorum > Self::reporters(env.clone()).len() {
+            return Err(Error::InvalidQuorum);
+        }
+
+        env.storage().instance().set(&DataKey::Quorum, &quorum);
+
+        env.events().publish((Symbol::new(&env, "quorum_set"),), quorum);
+
+        Ok(())
+    }
+
+    /// Set the deviation from the median that flags a report as an outlier (only admin)
+    /// Applied relative to the median price and as absolute bps to risk
+    pub fn set_outlier_threshold(env: Env, threshold_bps: u32) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::OutlierThreshold, &threshold_bps);
+
+        Ok(())
+    }
+
+    /// Set how long a round may collect reports before it expires unfinished (only admin)
+    pub fn set_round_timeout(env: Env, timeout_secs: u64) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        env.storage().instance().set(&DataKey::RoundTimeout, &timeout_secs);
+
+        env.events()
+            .publish((Symbol::new(&env, "round_timeout_set"),), timeout_secs);
+
+        Ok(())
+    }
+
+    /// Get the round timeout in seconds
+    pub fn round_timeout(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RoundTimeout)
+            .unwrap_or(DEFAULT_ROUND_TIMEOUT)
+    }
+
+    /// Get the reporter set
+    pub fn reporters(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Reporters)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the number of reports needed to finalize a round
+    pub fn quorum(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::Quorum).unwrap_or(1)
+    }
+
+    /// Get the current outlier threshold in basis points
+    pub fn outlier_threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::OutlierThreshold)
+            .unwrap_or(DEFAULT_OUTLIER_THRESHOLD)
+    }
+
+    /// Get the id of the round currently collecting reports for a series
+    pub fn current_round(env: Env, asset: Address) -> u32 {
+        Self::load(&env, &DataKey::Round(asset)).unwrap_or(1)
+    }
+
+    /// Get the reports submitted for a round of a series
+    pub fn round_reports(env: Env, asset: Address, round: u32) -> Vec<Report> {
+        Self::load(&env, &DataKey::RoundReports(asset, round)).unwrap_or(Vec::new(&env))
+    }
+
+    /// Submit a fair price and risk report for the current round of a series (reporters only)
+    /// Once quorum is reached the medians are published and a new round opens; a
+    /// round older than the timeout is expired and this report opens the next one.
+    /// Reports from addresses since removed from the set no longer count.
+    /// Returns true if this report finalized the round
+    pub fn submit_report(
+        env: Env,
+        reporter: Address,
+        asset: Address,
+        fair_price: i128,
+        default_risk: u32,
+    ) -> Result<bool, Error> {
+        reporter.require_auth();
+
+        if !Self::reporters(env.clone()).contains(&reporter) {
+            return Err(Error::NotReporter);
+        }
+
+        if fair_price <= 0 || default_risk > BPS {
+            return Err(Error::InvalidReport);
+        }
+
+        Self::series_state(&env, &asset)?;
+
+        let mut round = Self::current_round(env.clone(), asset.clone());
+        let submitted = Self::round_reports(env.clone(), asset.clone(), round);
+
+        let now = env.ledger().timestamp();
+        let opened_at: u64 = Self::load(&env, &DataKey::RoundOpenedAt(asset.clone())).unwrap_or(now);
+        if !submitted.is_empty() && now > opened_at + Self::round_timeout(env.clone()) {
+            env.events().publish(
+                (Symbol::new(&env, "round_expired"), asset.clone()),
+                (round, submitted.len()),
+            );
+            round += 1;
+            Self::save(&env, &DataKey::Round(asset.clone()), &round);
+        }
+
+        let reporters = Self::reporters(env.clone());
+        let mut reports = Vec::new(&env);
+        for report in Self::round_reports(env.clone(), asset.clone(), round).iter() {
+            if report.reporter == reporter {
+                return Err(Error::AlreadyReported);
+            }
+            if reporters.contains(&report.reporter) {
+                reports.push_back(report);
+            }
+        }
+
+        if reports.is_empty() {
+            Self::save(&env, &DataKey::RoundOpenedAt(asset.clone()), &now);
+        }
+
+        reports.push_back(Report {
+            reporter: reporter.clone(),
+            fair_price,
+            default_risk,
+        });
+        Self::save(&env, &DataKey::RoundReports(asset.clone(), round), &reports);
+
+        env.events().publish(
+            (Symbol::new(&env, "report_submitted"), asset.clone(), reporter),
+            (round, fair_price, default_risk),
+        );
+
+        if reports.len() < Self::quorum(env.clone()) {
+            return Ok(false);
+        }
+
+        Self::finalize_round(&env, &asset, round, &reports);
+        Ok(true)
+    }
+
+    /// Register an ed25519 public key allowed to sign reports (only admin)
+    pub fn add_signer(env: Env, public_key: BytesN<32>) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        let mut signers = Self::signers(env.clone());
+        if signers.contains(&public_key) {
+            return Err(Error::AlreadySigner);
+        }
+
+        signers.push_back(public_key.clone());
+        env.storage().instance().set(&DataKey::Signers, &signers);
+
+        env.events()
+            .publish((Symbol::new(&env, "signer_added"),), public_key);
+
+        Ok(())
+    }
+
+    /// Remove a registered signing key (only admin)
+    pub fn remove_signer(env: Env, public_key: BytesN<32>) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        let mut signers = Self::signers(env.clone());
+        let index = signers
+            .first_index_of(&public_key)
+            .ok_or(Error::UnknownSigner)?;
+
+        signers.remove(index);
+        env.storage().instance().set(&DataKey::Signers, &signers);
+
+        env.events()
+            .publish((Symbol::new(&env, "signer_removed"),), public_key);
+
+        Ok(())
+    }
+
+    /// Get registered signing keys
+    pub fn signers(env: Env) -> Vec<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Set how old a signed report may be when relayed (only admin)
+    pub fn set_max_report_age(env: Env, max_age_secs: u64) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        env.storage().instance().set(&DataKey::MaxReportAge, &max_age_secs);
+
+        env.events()
+            .publish((Symbol::new(&env, "max_report_age_set"),), max_age_secs);
+
+        Ok(())
+    }
+
+    /// Get the maximum age of a signed report in seconds
+    pub fn max_report_age(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxReportAge)
+            .unwrap_or(DEFAULT_MAX_REPORT_AGE)
+    }
+
+    /// Get the last nonce accepted from a signer (0 if none)
+    pub fn signer_nonce(env: Env, public_key: BytesN<32>) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SignerNonce(public_key))
+            .unwrap_or(0)
+    }
+
+    /// Bytes a signer must sign for a report: XDR of (oracle address, report)
+    /// Binding the oracle address stops a report being replayed on another deployment
+    pub fn report_payload(env: Env, report: SignedReport) -> Bytes {
+        (env.current_contract_address(), report).to_xdr(&env)
+    }
+
+    /// Publish a report signed off-chain by a registered key (callable by anyone)
+    /// Nonces must increase per signer and timestamps across all signed reports of a series
+    /// Disabled while a reporter set exists, so prices come from quorum rounds
+    pub fn submit_signed_report(
+        env: Env,
+        report: SignedReport,
+        public_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        Self::require_no_reporters(&env)?;
+
+        if !Self::signers(env.clone()).contains(&public_key) {
+            return Err(Error::UnknownSigner);
+        }
+
+        if report.nonce <= Self::signer_nonce(env.clone(), public_key.clone()) {
+            return Err(Error::ReplayedNonce);
+        }
+
+        Self::series_state(&env, &report.asset)?;
+
+        let timestamp_key = DataKey::LastSignedTimestamp(report.asset.clone());
+        let last_timestamp: u64 = Self::load(&env, &timestamp_key).unwrap_or(0);
+        if report.timestamp <= last_timestamp || report.timestamp > env.ledger().timestamp() {
+            return Err(Error::InvalidTimestamp);
+        }
+
+        if env.ledger().timestamp() - report.timestamp > Self::max_report_age(env.clone()) {
+            return Err(Error::Stale);
+        }
+
+        if report.fair_price <= 0 || report.default_risk > BPS {
+            return Err(Error::InvalidReport);
+        }
+
+        // Panics if the signature does not match
+        let payload = Self::report_payload(env.clone(), report.clone());
+        env.crypto().ed25519_verify(&public_key, &payload, &signature);
+
+        let key = DataKey::SignerNonce(public_key.clone());
+        env.storage().persistent().set(&key, &report.nonce);
+        Self::extend_persistent(&env, &key);
+        Self::save(&env, &timestamp_key, &report.timestamp);
+
+        Self::publish_or_hold(
+            &env,
+            &report.asset,
+            report.fair_price,
+            report.default_risk,
+            PriceTimestamp {
+                timestamp: report.timestamp,
+                ledger: env.ledger().sequence(),
+            },
+            false,
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "signed_report"), report.asset, public_key),
+            (report.nonce, report.timestamp),
+        );
+
+        Ok(())
+    }
+
+    /// Set the projected cashflow schedule and per-period discount rate of a series
+    /// (only its updater)
+    /// Periods must be strictly increasing, start at 1 or later and end by period 480
+    pub fn set_cashflow_schedule(
+        env: Env,
+        asset: Address,
+        cashflows: Vec<Cashflow>,
+        discount_rate_bps: u32,
+    ) -> Result<(), Error> {
+        Self::require_updater(&env, &asset)?;
+
+        if cashflows.is_empty() || cashflows.len() > MAX_CASHFLOWS || discount_rate_bps > BPS {
+            return Err(Error::InvalidSchedule);
+        }
+
+        let mut last_period = 0u32;
+        for cashflow in cashflows.iter() {
+            if cashflow.period <= last_period || cashflow.period > MAX_CASHFLOW_PERIOD || cashflow.amount < 0 {
+                return Err(Error::InvalidSchedule);
+            }
+            last_period = cashflow.period;
+        }
+
+        // Realized figures were measured against the old schedule: start over
+        for cashflow in Self::cashflow_schedule(env.clone(), asset.clone()).iter() {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Realized(asset.clone(), cashflow.period));
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Performance(asset.clone()));
+
+        Self::save(&env, &DataKey::Cashflows(asset.clone()), &cashflows);
+        Self::save(&env, &DataKey::DiscountRate(asset.clone()), &discount_rate_bps);
+
+        env.events().publish(
+            (Symbol::new(&env, "schedule_updated"), asset),
+            (cashflows.len(), discount_rate_bps),
+        );
+
+        Ok(())
+    }
+
+    /// Get the projected cashflow schedule of a series
+    pub fn cashflow_schedule(env: Env, asset: Address) -> Vec<Cashflow> {
+        Self::load(&env, &DataKey::Cashflows(asset)).unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the per-period discount rate of a series in basis points
+    pub fn discount_rate(env: Env, asset: Address) -> u32 {
+        Self::load(&env, &DataKey::DiscountRate(asset)).unwrap_or(0)
+    }
+
+    /// Risk-adjusted present value of the projected cashflows (7 decimals)
+    /// PV = Σ amount / (1 + r)^period, then multiplied by (1 - default_risk)
+    pub fn present_value(env: Env, asset: Address) -> Result<i128, Error> {
+        Self::extend_instance(&env);
+
+        let (_, present_value) = Self::discount_schedule(&env, &asset)?;
+        Ok(present_value)
+    }
+
+    /// Full price derivation from the stored schedule, risk and the series' DOB supply
+    /// Read-only so auditors can reproduce the published fair price
+    pub fn valuation(env: Env, asset: Address) -> Result<Valuation, Error> {
+        let (discounted_value, present_value) = Self::discount_schedule(&env, &asset)?;
+
+        let outstanding_supply: i128 = env.invoke_contract(
+            &asset,
+            &Symbol::new(&env, "total_supply"),
+            soroban_sdk::vec![&env],
+        );
+
+        if outstanding_supply <= 0 {
+            return Err(Error::NoSupply);
+        }
+
+        // Both PV and supply carry 7 decimals, so rescale to a 7 decimal price
+        let fair_price = (present_value * PRICE_SCALE) / outstanding_supply;
+
+        Ok(Valuation {
+            discounted_value,
+            present_value,
+            outstanding_supply,
+            discount_rate_bps: Self::discount_rate(env.clone(), asset.clone()),
+            default_risk: Self::series_state(&env, &asset)?.default_risk,
+            fair_price,
+        })
+    }
+
+    /// Recompute fair price of a series from its cashflow schedule and publish it
+    /// through the circuit breaker (only its updater)
+    /// Disabled while a reporter set exists, so prices come from quorum rounds
+    pub fn compute_fair_price(env: Env, asset: Address) -> Result<i128, Error> {
+        Self::require_updater(&env, &asset)?;
+        Self::require_no_reporters(&env)?;
+
+        let valuation = Self::valuation(env.clone(), asset.clone())?;
+
+        if valuation.fair_price <= 0 {
+            return Err(Error::InvalidSchedule);
+        }
+
+        Self::publish_or_hold(
+            &env,
+            &asset,
+            valuation.fair_price,
+            valuation.default_risk,
+            Self::now(&env),
+            false,
+        );
+        env.events().publish(
+            (Symbol::new(&env, "fair_price_computed"), asset),
+            valuation.clone(),
+        );
+
+        Ok(valuation.fair_price)
+    }
+
+    /// Set the contract allowed to apply amortization to a series (only admin)
+    pub fn set_amortizer(env: Env, asset: Address, amortizer: Address) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        Self::series_state(&env, &asset)?;
+        Self::save(&env, &DataKey::Amortizer(asset.clone()), &amortizer);
+
+        env.events()
+            .publish((Symbol::new(&env, "amortizer_set"), asset), amortizer);
+
+        Ok(())
+    }
+
+    /// Get the amortizer of a series, if set
+    pub fn amortizer(env: Env, asset: Address) -> Option<Address> {
+        Self::load(&env, &DataKey::Amortizer(asset))
+    }
+
+    /// Scale the remaining projected cashflows down in step with `retired`
+    /// DOB tokens the amortizer just burned, then republish the fair price
+    /// derived from the schedule (only amortizer)
+    /// Cashflows shrink by the same ratio as the supply, so PV per outstanding
+    /// token and the coverage ceiling hold between batches
+    /// While a reporter set exists the schedule is scaled but the price is left
+    /// to the next quorum round
+    pub fn apply_amortization(env: Env, asset: Address, retired: i128) -> Result<(), Error> {
+        let amortizer = Self::amortizer(env.clone(), asset.clone()).ok_or(Error::NotConfigured)?;
+        amortizer.require_auth();
+
+        if retired < 0 {
+            return Err(Error::InvalidSchedule);
+        }
+
+        let outstanding_supply: i128 = env.invoke_contract(
+            &asset,
+            &Symbol::new(&env, "total_supply"),
+            soroban_sdk::vec![&env],
+        );
+        let supply_before = outstanding_supply + retired;
+        if supply_before <= 0 {
+            return Err(Error::NoSupply);
+        }
+
+        let mut cashflows = Vec::new(&env);
+        for cashflow in Self::cashflow_schedule(env.clone(), asset.clone()).iter() {
+            cashflows.push_back(Cashflow {
+                period: cashflow.period,
+                amount: (cashflow.amount * outstanding_supply) / supply_before,
+            });
+        }
+        Self::save(&env, &DataKey::Cashflows(asset.clone()), &cashflows);
+
+        env.events()
+            .publish((Symbol::new(&env, "amortization_applied"), asset.clone(), amortizer), retired);
+
+        // Series priced from a schedule get their price refreshed through the breaker
+        if !cashflows.is_empty() && outstanding_supply > 0 && Self::reporters(env.clone()).is_empty() {
+            let valuation = Self::valuation(env.clone(), asset.clone())?;
+            if valuation.fair_price > 0 {
+                Self::publish_or_hold(
+                    &env,
+                    &asset,
+                    valuation.fair_price,
+                    valuation.default_risk,
+                    Self::now(&env),
+                    false,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record revenue received for a schedule period of a series (only its updater)
+    /// With an underperformance policy set, a run of short periods raises
+    /// default risk through the circuit breaker
+    pub fn report_realized_cashflow(
+        env: Env,
+        asset: Address,
+        period: u32,
+        amount: i128,
+        evidence_hash: BytesN<32>,
+    ) -> Result<RealizedCashflow, Error> {
+        let state = Self::require_updater(&env, &asset)?;
+
+        if amount < 0 {
+            return Err(Error::InvalidReport);
+        }
+
+        let realized_key = DataKey::Realized(asset.clone(), period);
+        if env.storage().persistent().has(&realized_key) {
+            return Err(Error::AlreadyReported);
+        }
+
+        let projected = Self::cashflow_schedule(env.clone(), asset.clone())
+            .iter()
+            .find(|cashflow| cashflow.period == period)
+            .ok_or(Error::UnknownPeriod)?
+            .amount;
+
+        let realized = RealizedCashflow {
+            period,
+            projected,
+            amount,
+            evidence_hash,
+            reported_at: env.ledger().timestamp(),
+        };
+        Self::save(&env, &realized_key, &realized);
+
+        let policy = Self::underperformance_policy(env.clone());
+        let mut performance = Self::cashflow_performance(env.clone(), asset.clone());
+        performance.periods_reported += 1;
+        performance.projected_total += projected;
+        performance.realized_total += amount;
+        performance.variance = performance.realized_total - performance.projected_total;
+        performance.variance_bps = if performance.projected_total > 0 {
+            (performance.variance * BPS as i128) / performance.projected_total
+        } else {
+            0
+        };
+
+        if let Some(policy) = &policy {
+            performance.consecutive_shortfalls = Self::shortfall_run(&env, &asset, policy);
+        }
+        Self::save(&env, &DataKey::Performance(asset.clone()), &performance);
+
+        env.events().publish(
+            (Symbol::new(&env, "cashflow_realized"), asset.clone(), period),
+            realized.clone(),
+        );
+
+        if let Some(policy) = policy {
+            if performance.consecutive_shortfalls >= policy.consecutive_periods {
+                let current_risk = state.default_risk;
+                let raised_risk = (current_risk + policy.risk_step_bps).min(BPS);
+
+                if raised_risk != current_risk {
+                    env.events().publish(
+                        (Symbol::new(&env, "risk_escalated"), asset.clone(), period),
+                        (current_risk, raised_risk),
+                    );
+                    // Only the risk moves: keep the price's last update so a
+                    // stale feed is not made to look fresh
+                    Self::publish_or_hold(
+                        &env,
+                        &asset,
+                        state.fair_price,
+                        raised_risk,
+                        state.last_updated.clone(),
+                        false,
+                    );
+                }
+            }
+        }
+
+        Ok(realized)
+    }
+
+    /// Get the realized cashflow reported for a period of a series, if any
+    pub fn realized_cashflow(env: Env, asset: Address, period: u32) -> Option<RealizedCashflow> {
+        Self::load(&env, &DataKey::Realized(asset, period))
+    }
+
+    /// Cumulative realized vs projected performance of a series
+    pub fn cashflow_performance(env: Env, asset: Address) -> CashflowPerformance {
+        Self::load(&env, &DataKey::Performance(asset)).unwrap_or(CashflowPerformance {
+            periods_reported: 0,
+            projected_total: 0,
+            realized_total: 0,
+            variance: 0,
+            variance_bps: 0,
+            consecutive_shortfalls: 0,
+        })
+    }
+
+    /// Feed persistent underperformance into default risk (only admin)
+    pub fn set_underperformance_policy(env: Env, policy: UnderperformancePolicy) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        if policy.shortfall_threshold_bps > BPS || policy.consecutive_periods == 0 || policy.risk_step_bps > BPS {
+            return Err(Error::InvalidReport);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::UnderperformancePolicy, &policy);
+
+        Ok(())
+    }
+
+    /// Stop adjusting default risk from realized cashflows (only admin)
+    pub fn clear_underperformance_policy(env: Env) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::UnderperformancePolicy);
+
+        Ok(())
+    }
+
+    /// Get the underperformance policy, if one is set
+    pub fn underperformance_policy(env: Env) -> Option<UnderperformancePolicy> {
+        env.storage()
+            .instance()
+            .get(&DataKey::UnderperformancePolicy)
+    }
+
+    /// Replace the redemption penalty curve of a series (only admin)
+    /// Breakpoints must have strictly increasing risk within 0..=10000 and
+    /// penalties no higher than 50%
+    pub fn set_penalty_curve(env: Env, asset: Address, points: Vec<PenaltyPoint>) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        if points.is_empty() || points.len() > MAX_PENALTY_POINTS {
+            return Err(Error::InvalidPenaltyCurve);
+        }
+
+        let mut previous_risk: Option<u32> = None;
+        for point in points.iter() {
+            if point.risk_bps > BPS || point.penalty_bps > MAX_PENALTY_BPS {
+                return Err(Error::InvalidPenaltyCurve);
+            }
+            if let Some(previous) = previous_risk {
+                if point.risk_bps <= previous {
+                    return Err(Error::InvalidPenaltyCurve);
+                }
+            }
+            previous_risk = Some(point.risk_bps);
+        }
+
+        Self::series_state(&env, &asset)?;
+        Self::save(&env, &DataKey::PenaltyCurve(asset.clone()), &points);
+
+        env.events()
+            .publish((Symbol::new(&env, "penalty_curve_set"), asset), points);
+
+        Ok(())
+    }
+
+    /// Get the redemption penalty curve of a series
+    /// Defaults to 3% base + risk/10, i.e. a line from (0, 300) to (10000, 1300)
+    pub fn penalty_curve(env: Env, asset: Address) -> Vec<PenaltyPoint> {
+        Self::load(&env, &DataKey::PenaltyCurve(asset)).unwrap_or(soroban_sdk::vec![
+            &env,
+            PenaltyPoint { risk_bps: 0, penalty_bps: 300 },
+            PenaltyPoint { risk_bps: BPS, penalty_bps: 1300 },
+        ])
+    }
+
+    /// Calculate redemption penalty for a default risk by evaluating the series' curve
+    /// Returns penalty in basis points (10000 = 100%); flat beyond the end points
+    pub fn calculate_penalty(env: Env, asset: Address, risk: u32) -> u32 {
+        Self::extend_instance(&env);
+
+        let curve = Self::penalty_curve(env, asset);
+
+        let first = curve.get_unchecked(0);
+        if risk <= first.risk_bps {
+            return first.penalty_bps;
+        }
+
+        for i in 1..curve.len() {
+            let upper = curve.get_unchecked(i);
+            if risk <= upper.risk_bps {
+                let lower = curve.get_unchecked(i - 1);
+                // Linear interpolation between the surrounding breakpoints
+                let span = (upper.risk_bps - lower.risk_bps) as i64;
+                let offset = (risk - lower.risk_bps) as i64;
+                let delta = upper.penalty_bps as i64 - lower.penalty_bps as i64;
+                return (lower.penalty_bps as i64 + (delta * offset) / span) as u32;
+            }
+        }
+
+        curve.get_unchecked(curve.len() - 1).penalty_bps
+    }
+
+    /// Redemption penalty for the current default risk of a series
+    pub fn current_penalty(env: Env, asset: Address) -> Result<u32, Error> {
+        let risk = Self::default_risk(env.clone(), asset.clone())?;
+        Ok(Self::calculate_penalty(env, asset, risk))
+    }
+}
+
+/// Credit lifecycle of each series
+/// Consumers stop issuance once it is impaired and redeem at the recovery price
+#[contractimpl]
+impl DobOracle {
+    /// Move a series to a new credit state (only admin)
+    ///
+    /// Allowed transitions:
+    /// Performing -> Watchlist | Matured
+    /// Watchlist  -> Performing | Delinquent
+    /// Delinquent -> Watchlist | Performing | Defaulted
+    /// Defaulted  -> Recovered
+    /// Recovered  -> Matured
+    /// Entering Defaulted requires a recovery price to be set
+    pub fn transition_credit_state(
+        env: Env,
+        asset: Address,
+        new_state: CreditState,
+        reason_code: u32,
+    ) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        Self::series_state(&env, &asset)?;
+
+        let current = Self::credit_status(env.clone(), asset.clone());
+        if !Self::is_allowed_transition(current.state, new_state) {
+            return Err(Error::InvalidTransition);
+        }
+
+        if new_state == CreditState::Defaulted && Self::recovery_price(env.clone(), asset.clone()).is_none() {
+            return Err(Error::NotConfigured);
+        }
+
+        let status = CreditStatus {
+            state: new_state,
+            reason_code,
+            since: env.ledger().timestamp(),
+        };
+
+        let mut history = Self::credit_history(env.clone(), asset.clone());
+        history.push_back(current.clone());
+        Self::save(&env, &DataKey::CreditHistory(asset.clone()), &history);
+        Self::save(&env, &DataKey::CreditStatus(asset.clone()), &status);
+
+        env.events().publish(
+            (Symbol::new(&env, "credit_state_changed"), asset, current.state),
+            status,
+        );
+
+        Ok(())
+    }
+
+    /// Set the price redemptions of a series settle at while Defaulted or Recovered
+    /// (only admin)
+    pub fn set_recovery_price(env: Env, asset: Address, price: i128) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        if price < 0 {
+            return Err(Error::InvalidReport);
+        }
+
+        Self::series_state(&env, &asset)?;
+        Self::save(&env, &DataKey::RecoveryPrice(asset.clone()), &price);
+
+        env.events()
+            .publish((Symbol::new(&env, "recovery_price_set"), asset), price);
+
+        Ok(())
+    }
+
+    /// Set the final price matured DOB tokens of a series redeem at (only admin)
+    /// The price is also published as the fair price and passes the circuit
+    /// breaker like any other update; it takes effect once published and
+    /// cannot be changed afterwards
+    pub fn set_settlement_price(env: Env, asset: Address, price: i128) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        if price <= 0 {
+            return Err(Error::InvalidReport);
+        }
+
+        if Self::settlement_price(env.clone(), asset.clone()).is_some() {
+            return Err(Error::AlreadySettled);
+        }
+
+        let state = Self::series_state(&env, &asset)?;
+        Self::publish_or_hold(
+            &env,
+            &asset,
+            price,
+            state.default_risk,
+            Self::now(&env),
+            true,
+        );
+
+        Ok(())
+    }
+
+    /// Get the final settlement price of a series, if set
+    pub fn settlement_price(env: Env, asset: Address) -> Option<i128> {
+        Self::extend_instance(&env);
+        Self::load(&env, &DataKey::SettlementPrice(asset))
+    }
+
+    /// Get the current credit state of a series with its reason code and timestamp
+    pub fn credit_status(env: Env, asset: Address) -> CreditStatus {
+        Self::extend_instance(&env);
+        Self::load(&env, &DataKey::CreditStatus(asset)).unwrap_or(CreditStatus {
+            state: CreditState::Performing,
+            reason_code: 0,
+            since: 0,
+        })
+    }
+
+    /// Get the current credit state of a series
+    pub fn credit_state(env: Env, asset: Address) -> CreditState {
+        Self::credit_status(env, asset).state
+    }
+
+    /// Get the states a series has left, oldest first
+    pub fn credit_history(env: Env, asset: Address) -> Vec<CreditStatus> {
+        Self::load(&env, &DataKey::CreditHistory(asset)).unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the recovery price of a series, if set
+    pub fn recovery_price(env: Env, asset: Address) -> Option<i128> {
+        Self::load(&env, &DataKey::RecoveryPrice(asset))
+    }
+
+    /// Whether new tokens may still be issued against a series
+    pub fn issuance_open(env: Env, asset: Address) -> bool {
+        matches!(
+            Self::credit_state(env, asset),
+            CreditState::Performing | CreditState::Watchlist | CreditState::Delinquent
+        )
+    }
+
+    /// Price redemptions of a series must settle at instead of the market price:
+    /// the recovery price while Defaulted or Recovered, otherwise None
+    pub fn recovery_redemption_price(env: Env, asset: Address) -> Result<Option<i128>, Error> {
+        Self::require_live(&env)?;
+
+        match Self::credit_state(env.clone(), asset.clone()) {
+            CreditState::Defaulted | CreditState::Recovered => Ok(Self::recovery_price(env, asset)),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Registry of bond series keyed by their DOB token address
+/// Every registered series gets the full pricing, reporting and credit API
+#[contractimpl]
+impl DobOracle {
+    /// Register a bond series with its own updater and initial values (only admin)
+    pub fn register_asset(
+        env: Env,
+        asset: Address,
+        updater: Address,
+        fair_price: i128,
+        default_risk: u32,
+    ) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        if env.storage().persistent().has(&DataKey::Series(asset.clone())) {
+            return Err(Error::AssetExists);
+        }
+
+        if fair_price <= 0 || default_risk > BPS {
+            return Err(Error::InvalidReport);
+        }
+
+        Self::add_series(&env, &asset, &updater, fair_price, default_risk);
+
+        env.events().publish(
+            (Symbol::new(&env, "asset_registered"), asset),
+            OracleUpdate {
+                fair_price,
+                default_risk,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Remove a bond series from the registry (only admin)
+    /// Its history and credit record are kept, so re-registering the same token resumes them
+    pub fn remove_asset(env: Env, asset: Address) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        let mut series = Self::registered_assets(env.clone());
+        let index = series.first_index_of(&asset).ok_or(Error::AssetNotFound)?;
+
+        series.remove(index);
+        env.storage().instance().set(&DataKey::SeriesList, &series);
+        env.storage().persistent().remove(&DataKey::Series(asset.clone()));
+
+        // Drop anything that could still publish to the removed series; the round
+        // counter moves past the open round so a re-registered series starts clean
+        let round = Self::current_round(env.clone(), asset.clone());
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingUpdate(asset.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RoundReports(asset.clone(), round));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RoundOpenedAt(asset.clone()));
+        Self::save(&env, &DataKey::Round(asset.clone()), &(round + 1));
+
+        env.events()
+            .publish((Symbol::new(&env, "asset_removed"),), asset);
+
+        Ok(())
+    }
+
+    /// Change the updater of a bond series (only admin)
+    pub fn set_asset_updater(env: Env, asset: Address, updater: Address) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        let mut state = Self::series_state(&env, &asset)?;
+        state.updater = updater.clone();
+        Self::save(&env, &DataKey::Series(asset.clone()), &state);
+
+        env.events()
+            .publish((Symbol::new(&env, "asset_updater_changed"), asset), updater);
+
+        Ok(())
+    }
+
+    /// Get the full price state of a bond series
+    pub fn series(env: Env, asset: Address) -> Result<SeriesState, Error> {
+        Self::series_state(&env, &asset)
+    }
+
+    /// Get all registered bond series
+    pub fn registered_assets(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::SeriesList)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a page of registered bond series
+    pub fn registered_assets_page(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let series = Self::registered_assets(env.clone());
+        let end = start.saturating_add(limit).min(series.len());
+        if start >= end {
+            return Vec::new(&env);
+        }
+        series.slice(start..end)
+    }
+
+    /// Get the number of registered bond series
+    pub fn asset_count(env: Env) -> u32 {
+        Self::registered_assets(env).len()
+    }
+}
+
+/// SEP-40 price feed interface
+/// Exposes every registered series with its full price history
+#[contractimpl]
+impl DobOracle {
+    /// Set the asset fair prices are quoted in (only admin)
+    pub fn set_base_asset(env: Env, base: Asset) -> Result<(), Error> {
+        Self::admin(env.clone()).require_auth();
+
+        env.storage().instance().set(&DataKey::BaseAsset, &base);
+
+        Ok(())
+    }
+
+    /// SEP-40: asset prices are quoted in (defaults to Other("USDC"))
+    pub fn base(env: Env) -> Asset {
+        env.storage()
+            .instance()
+            .get(&DataKey::BaseAsset)
+            .unwrap_or(Asset::Other(Symbol::new(&env, "USDC")))
+    }
+
+    /// SEP-40: assets this oracle prices
+    pub fn assets(env: Env) -> Vec<Asset> {
+        let mut assets = Vec::new(&env);
+        for series in Self::registered_assets(env.clone()).iter() {
+            assets.push_back(Asset::Stellar(series));
+        }
+        assets
+    }
+
+    /// SEP-40: decimals of returned prices
+    pub fn decimals(_env: Env) -> u32 {
+        PRICE_DECIMALS
+    }
+
+    /// SEP-40: expected seconds between price updates
+    pub fn resolution(_env: Env) -> u32 {
+        RESOLUTION_SECS
+    }
+
+    /// SEP-40: most recent price of an asset
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        let address = Self::priced_address(&env, &asset)?;
+
+        Some(Self::price_data(&Self::observation(&env, &address, 0)))
+    }
+
+    /// SEP-40: price of an asset in effect at a timestamp
+    pub fn price(env: Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        let address = Self::priced_address(&env, &asset)?;
+
+        for back in 0..Self::history_len(&env, &address) {
+            let observation = Self::observation(&env, &address, back);
+            if observation.timestamp <= timestamp {
+                return Some(Self::price_data(&observation));
+            }
+        }
+
+        None
+    }
+
+    /// SEP-40: up to `records` most recent prices of an asset, newest first
+    pub fn prices(env: Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        let address = Self::priced_address(&env, &asset)?;
+
+        let mut prices = Vec::new(&env);
+        for observation in Self::recent_observations(&env, &address, records).iter() {
+            prices.push_back(Self::price_data(&observation));
+        }
+        Some(prices)
+    }
+
+    /// Set the storage TTL thresholds (only admin)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if config.instance_threshold > config.instance_extend_to
+            || config.persistent_threshold > config.persistent_extend_to
+            || config.instance_extend_to > env.storage().max_ttl()
+            || config.persistent_extend_to > env.storage().max_ttl()
+        {
+            return Err(Error::InvalidTtl);
+        }
+
+        env.storage().instance().set(&DataKey::TtlConfig, &config);
+        Self::extend_instance(&env);
+
+        env.events().publish(
+            (Symbol::new(&env, "ttl_config_set"), admin),
+            config,
+        );
+
+        Ok(())
+    }
+
+    /// Get the storage TTL thresholds
+    pub fn ttl_config(env: Env) -> TtlConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TtlConfig)
+            .unwrap_or(TtlConfig {
+                instance_threshold: INSTANCE_LIFETIME_THRESHOLD,
+                instance_extend_to: INSTANCE_BUMP_AMOUNT,
+                persistent_threshold: PERSISTENT_LIFETIME_THRESHOLD,
+                persistent_extend_to: PERSISTENT_BUMP_AMOUNT,
+            })
+    }
+
+    /// Replace the contract code (only admin)
+    /// Call `migrate` afterwards when the new code raises the schema version
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "upgraded"), admin),
+            new_wasm_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Apply storage layout changes up to the current schema version (only admin)
+    /// Contracts initialized before versioning report version 0
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let from_version = Self::schema_version(env.clone());
+        if from_version >= SCHEMA_VERSION {
+            return Err(Error::AlreadyMigrated);
+        }
+
+        // Version 1 introduced no layout change; later steps go here keyed on `from_version`
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+
+        env.events().publish(
+            (Symbol::new(&env, "migrated"), admin),
+            (from_version, SCHEMA_VERSION),
+        );
+
+        Ok(SCHEMA_VERSION)
+    }
+
+    /// Get the storage layout version
+    pub fn schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(0)
+    }
+}
+
+impl DobOracle {
+    fn is_allowed_transition(from: CreditState, to: CreditState) -> bool {
+        use CreditState::*;
+
+        matches!(
+            (from, to),
+            (Performing, Watchlist)
+                | (Performing, Matured)
+                | (Watchlist, Performing)
+                | (Watchlist, Delinquent)
+                | (Delinquent, Watchlist)
+                | (Delinquent, Performing)
+                | (Delinquent, Defaulted)
+                | (Defaulted, Recovered)
+                | (Recovered, Matured)
+        )
+    }
+
+    fn require_live(env: &Env) -> Result<(), Error> {
+        Self::extend_instance(env);
+
+        if Self::is_frozen(env.clone()) {
+            return Err(Error::Paused);
+        }
+        Ok(())
+    }
+
+    /// Fail with `Error::ReportersActive` while prices must come from quorum rounds
+    fn require_no_reporters(env: &Env) -> Result<(), Error> {
+        if !Self::reporters(env.clone()).is_empty() {
+            return Err(Error::ReportersActive);
+        }
+        Ok(())
+    }
+
+    fn series_state(env: &Env, asset: &Address) -> Result<SeriesState, Error> {
+        Self::load(env, &DataKey::Series(asset.clone())).ok_or(Error::AssetNotFound)
+    }
+
+    /// Require the auth of a series' updater and return its state
+    fn require_updater(env: &Env, asset: &Address) -> Result<SeriesState, Error> {
+        let state = Self::series_state(env, asset)?;
+        state.updater.require_auth();
+        Ok(state)
+    }
+
+    /// Store a new series with its first observation and a Performing credit state
+    fn add_series(env: &Env, asset: &Address, updater: &Address, fair_price: i128, default_risk: u32) {
+        let state = SeriesState {
+            fair_price,
+            default_risk,
+            updater: updater.clone(),
+            last_updated: Self::now(env),
+        };
+        Self::save(env, &DataKey::Series(asset.clone()), &state);
+        Self::record_observation(env, asset, fair_price, default_risk);
+
+        if !env.storage().persistent().has(&DataKey::CreditStatus(asset.clone())) {
+            Self::save(
+                env,
+                &DataKey::CreditStatus(asset.clone()),
+                &CreditStatus {
+                    state: CreditState::Performing,
+                    reason_code: 0,
+                    since: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        let mut series = Self::registered_assets(env.clone());
+        series.push_back(asset.clone());
+        env.storage().instance().set(&DataKey::SeriesList, &series);
+    }
+
+    fn recent_observations(env: &Env, asset: &Address, limit: u32) -> Vec<PriceObservation> {
+        let count = limit.min(Self::history_len(env, asset));
+        let mut history = Vec::new(env);
+        for back in 0..count {
+            history.push_back(Self::observation(env, asset, back));
+        }
+        history
+    }
+
+    /// Address of a SEP-40 asset if it is a registered series with history
+    /// and the oracle is not frozen
+    fn priced_address(env: &Env, asset: &Asset) -> Option<Address> {
+        if Self::is_frozen(env.clone()) {
+            return None;
+        }
+
+        match asset {
+            Asset::Stellar(address) => {
+                if Self::series_state(env, address).is_ok() && Self::history_len(env, address) > 0 {
+                    Some(address.clone())
+                } else {
+                    None
+                }
+            }
+            Asset::Other(_) => None,
+        }
+    }
+
+    fn price_data(observation: &PriceObservation) -> PriceData {
+        PriceData {
+            price: observation.fair_price,
+            timestamp: observation.timestamp,
+        }
+    }
+
+    /// Store a new fair price and risk, record it in history and emit the update
+    /// `observed_at` is when the values were produced and drives staleness checks
+    fn publish_price(
+        env: &Env,
+        asset: &Address,
+        fair_price: i128,
+        default_risk: u32,
+        observed_at: &PriceTimestamp,
+    ) {
+        let mut state = Self::series_state(env, asset).expect("Series not registered");
+        state.fair_price = fair_price;
+        state.default_risk = default_risk;
+        state.last_updated = observed_at.clone();
+        Self::save(env, &DataKey::Series(asset.clone()), &state);
+        Self::record_observation(env, asset, fair_price, default_risk);
+        Self::extend_instance(env);
+
+        env.events().publish(
+            (Symbol::new(env, "oracle_updated"), asset.clone()),
+            OracleUpdate {
+                fair_price,
+                default_risk,
+            },
+        );
+    }
+
+    /// Append an observation to the ring buffer, overwriting the oldest slot
+    /// Several updates within one ledger timestamp collapse into one observation
+    fn record_observation(env: &Env, asset: &Address, fair_price: i128, default_risk: u32) {
+        let timestamp = env.ledger().timestamp();
+        let len = Self::history_len(env, asset);
+        let head: u32 = Self::load(env, &DataKey::HistoryHead(asset.clone())).unwrap_or(0);
+
+        let slot = if len > 0 && Self::observation(env, asset, 0).timestamp == timestamp {
+            head
+        } else if len == 0 {
+            0
+        } else {
+            (head + 1) % HISTORY_CAPACITY
+        };
+
+        Self::save(
+            env,
+            &DataKey::Observation(asset.clone(), slot),
+            &PriceObservation {
+                timestamp,
+                fair_price,
+                default_risk,
+            },
+        );
+        Self::save(env, &DataKey::HistoryHead(asset.clone()), &slot);
+        if slot != head || len == 0 {
+            Self::save(
+                env,
+                &DataKey::HistoryLen(asset.clone()),
+                &(len + 1).min(HISTORY_CAPACITY),
+            );
+        }
+    }
+
+    /// Publish an update if it is within the deviation limits, otherwise hold it
+    /// as the series' pending update (replacing and rejecting any earlier pending one)
+    /// A `settles` update also fixes the settlement price when it is published
+    fn publish_or_hold(
+        env: &Env,
+        asset: &Address,
+        fair_price: i128,
+        default_risk: u32,
+        observed_at: PriceTimestamp,
+        settles: bool,
+    ) {
+        let limits: Option<DeviationLimits> = env.storage().instance().get(&DataKey::DeviationLimits);
+
+        if let Some(limits) = limits {
+            let current = Self::series_state(env, asset).expect("Series not registered");
+
+            let price_deviation = ((fair_price - current.fair_price).abs() * BPS as i128) / current.fair_price;
+            let risk_deviation = default_risk.abs_diff(current.default_risk);
+
+            if price_deviation > limits.max_price_deviation_bps as i128
+                || risk_deviation > limits.max_risk_deviation_bps
+            {
+                if let Some(previous) = Self::pending_update(env.clone(), asset.clone()) {
+                    env.events().publish(
+                        (Symbol::new(env, "oracle_update_rejected"), asset.clone()),
+                        OracleUpdate {
+                            fair_price: previous.fair_price,
+                            default_risk: previous.default_risk,
+                        },
+                    );
+                }
+
+                let now = env.ledger().timestamp();
+                let pending = PendingUpdate {
+                    fair_price,
+                    default_risk,
+                    proposed_at: now,
+                    executable_at: now + Self::pending_timelock(env.clone()),
+                    observed_at,
+                    settles,
+                };
+                Self::save(env, &DataKey::PendingUpdate(asset.clone()), &pending);
+
+                env.events()
+                    .publish((Symbol::new(env, "oracle_update_pending"), asset.clone()), pending);
+                return;
+            }
+        }
+
+        Self::publish_pending(
+            env,
+            asset,
+            &PendingUpdate {
+                fair_price,
+                default_risk,
+                proposed_at: env.ledger().timestamp(),
+                executable_at: env.ledger().timestamp(),
+                observed_at,
+                settles,
+            },
+        );
+    }
+
+    /// Publish an update that passed or cleared the circuit breaker
+    fn publish_pending(env: &Env, asset: &Address, update: &PendingUpdate) {
+        Self::publish_price(env, asset, update.fair_price, update.default_risk, &update.observed_at);
+
+        // Write-once: a settlement held by the breaker cannot replace one published since
+        if update.settles && !env.storage().persistent().has(&DataKey::SettlementPrice(asset.clone())) {
+            Self::save(env, &DataKey::SettlementPrice(asset.clone()), &update.fair_price);
+            env.events()
+                .publish((Symbol::new(env, "settlement_price_set"), asset.clone()), update.fair_price);
+        }
+    }
+
+    /// Publish the medians of a round, flag outliers and open the next round
+    fn finalize_round(env: &Env, asset: &Address, round: u32, reports: &Vec<Report>) {
+        let mut prices = Vec::new(env);
+        let mut risks = Vec::new(env);
+        for report in reports.iter() {
+            prices.push_back(report.fair_price);
+            risks.push_back(report.default_risk as i128);
+        }
+
+        let median_price = Self::median(prices);
+        let median_risk = Self::median(risks) as u32;
+
+        let threshold = Self::outlier_threshold(env.clone()) as i128;
+        for report in reports.iter() {
+            let price_deviation = ((report.fair_price - median_price).abs() * BPS as i128) / median_price;
+            let risk_deviation = (report.default_risk as i128 - median_risk as i128).abs();
+
+            if price_deviation > threshold || risk_deviation > threshold {
+                env.events().publish(
+                    (Symbol::new(env, "reporter_outlier"), asset.clone(), report.reporter.clone()),
+                    OutlierReport {
+                        round,
+                        fair_price: report.fair_price,
+                        default_risk: report.default_risk,
+                        median_price,
+                        median_risk,
+                    },
+                );
+            }
+        }
+
+        Self::publish_or_hold(env, asset, median_price, median_risk, Self::now(env), false);
+        Self::save(env, &DataKey::Round(asset.clone()), &(round + 1));
+
+        env.events().publish(
+            (Symbol::new(env, "round_finalized"), asset.clone()),
+            RoundResult {
+                round,
+                fair_price: median_price,
+                default_risk: median_risk,
+                report_count: reports.len(),
+            },
+        );
+    }
+
+    /// Median of a non-empty list; even-sized lists average the middle pair
+    fn median(mut values: Vec<i128>) -> i128 {
+        // Insertion sort: report sets are small
+        for i in 1..values.len() {
+            let current = values.get_unchecked(i);
+            let mut j = i;
+            while j > 0 && values.get_unchecked(j - 1) > current {
+                values.set(j, values.get_unchecked(j - 1));
+                j -= 1;
+            }
+            values.set(j, current);
+        }
+
+        let mid = values.len() / 2;
+        if values.len().is_multiple_of(2) {
+            (values.get_unchecked(mid - 1) + values.get_unchecked(mid)) / 2
+        } else {
+            values.get_unchecked(mid)
+        }
+    }
+
+    fn now(env: &Env) -> PriceTimestamp {
+        PriceTimestamp {
+            timestamp: env.ledger().timestamp(),
+            ledger: env.ledger().sequence(),
+        }
+    }
+
+    fn history_len(env: &Env, asset: &Address) -> u32 {
+        Self::load(env, &DataKey::HistoryLen(asset.clone())).unwrap_or(0)
+    }
+
+    /// Observation `back` steps behind the newest one (0 = newest)
+    fn observation(env: &Env, asset: &Address, back: u32) -> PriceObservation {
+        let head: u32 = Self::load(env, &DataKey::HistoryHead(asset.clone())).unwrap_or(0);
+        let slot = (head + HISTORY_CAPACITY - back) % HISTORY_CAPACITY;
+        Self::load(env, &DataKey::Observation(asset.clone(), slot)).expect("Observation missing")
+    }
+
+    /// Read a per-series entry, extending its TTL when present
+    fn load<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+        let value = env.storage().persistent().get(key);
+        if value.is_some() {
+            Self::extend_persistent(env, key);
+        }
+        value
+    }
+
+    /// Write a per-series entry and extend its TTL
+    fn save<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+        env.storage().persistent().set(key, value);
+        Self::extend_persistent(env, key);
+    }
+
+    fn extend_instance(env: &Env) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .instance()
+            .extend_ttl(config.instance_threshold, config.instance_extend_to);
+    }
+
+    /// Extend an existing persistent entry
+    fn extend_persistent(env: &Env, key: &DataKey) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .persistent()
+            .extend_ttl(key, config.persistent_threshold, config.persistent_extend_to);
+    }
+
+    /// Short periods in a row, in schedule order, ending at the latest reported
+    /// period; an unreported period inside the run ends it
+    fn shortfall_run(env: &Env, asset: &Address, policy: &UnderperformancePolicy) -> u32 {
+        let schedule = Self::cashflow_schedule(env.clone(), asset.clone());
+        let mut run = 0u32;
+        let mut reached_latest = false;
+
+        for index in (0..schedule.len()).rev() {
+            let period = schedule.get_unchecked(index).period;
+            match Self::realized_cashflow(env.clone(), asset.clone(), period) {
+                Some(realized) => {
+                    reached_latest = true;
+                    let floor = (realized.projected * (BPS - policy.shortfall_threshold_bps) as i128) / BPS as i128;
+                    if realized.amount >= floor {
+                        break;
+                    }
+                    run += 1;
+                }
+                None if reached_latest => break,
+                None => {}
+            }
+        }
+
+        run
+    }
+
+    /// Returns (discounted value, risk-adjusted present value) of a series' schedule
+    fn discount_schedule(env: &Env, asset: &Address) -> Result<(i128, i128), Error> {
+        let cashflows: Vec<Cashflow> =
+            Self::load(env, &DataKey::Cashflows(asset.clone())).ok_or(Error::NotConfigured)?;
+        let rate = Self::discount_rate(env.clone(), asset.clone()) as i128;
+
+        // Discount factor 1 / (1 + r)^period, carried in 7 decimals and
+        // advanced one period at a time so it never needs a pow()
+        let mut discount_factor = PRICE_SCALE;
+        let mut factor_period = 0u32;
+        let mut discounted_value = 0i128;
+
+        for cashflow in cashflows.iter() {
+            while factor_period < cashflow.period {
+                discount_factor = (discount_factor * BPS as i128) / (BPS as i128 + rate);
+                factor_period += 1;
+            }
+            discounted_value += (cashflow.amount * discount_factor) / PRICE_SCALE;
+        }
+
+        // Survival-probability haircut: only (1 - default_risk) of value is expected
+        let default_risk = Self::series_state(env, asset)?.default_risk;
+        let survival_bps = (BPS - default_risk.min(BPS)) as i128;
+        let present_value = (discounted_value * survival_bps) / BPS as i128;
+
+        Ok((discounted_value, present_value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use soroban_sdk::{symbol_short, testutils::{storage::Instance as _, Address as _, Ledger}};
+
+    /// Minimal token exposing total_supply for valuation tests
+    #[contract]
+    pub struct MockDobToken;
+
+    #[contractimpl]
+    impl MockDobToken {
+        pub fn set_total_supply(env: Env, supply: i128) {
+            env.storage().instance().set(&symbol_short!("supply"), &supply);
+        }
+
+        pub fn total_supply(env: Env) -> i128 {
+            env.storage().instance().get(&symbol_short!("supply")).unwrap_or(0)
+        }
+    }
+
+    #[test]
+    fn test_initialize() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        // Initialize with 1.00 fair price and 10% risk
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+
+        assert_eq!(client.fair_price(&dob_token), 10_000_000);
+        assert_eq!(client.default_risk(&dob_token), 1000);
+        assert_eq!(client.updater(&dob_token), updater);
+    }
+
+    #[test]
+    fn test_update() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+
+        // Update to fair price 1.15 and 7% risk
+        client.update(&dob_token, &11_500_000, &700);
+
+        assert_eq!(client.fair_price(&dob_token), 11_500_000);
+        assert_eq!(client.default_risk(&dob_token), 700);
+    }
+
+    #[test]
+    fn test_calculate_penalty() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        // 10% default risk
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+        // Penalty = 300 + 1000/10 = 400 bps = 4%
+        assert_eq!(client.calculate_penalty(&dob_token, &1000), 400);
+        assert_eq!(client.current_penalty(&dob_token), 400);
+
+        // 35% default risk
+        client.update(&dob_token, &10_000_000, &3500);
+        // Penalty = 300 + 3500/10 = 650 bps = 6.5%
+        assert_eq!(client.calculate_penalty(&dob_token, &3500), 650);
+        assert_eq!(client.current_penalty(&dob_token), 650);
+
+        // Very high risk (60%)
+        client.update(&dob_token, &10_000_000, &6000);
+        // Penalty = 300 + 6000/10 = 900, but capped at 5000 (50%)
+        assert_eq!(client.calculate_penalty(&dob_token, &6000), 900);
+        assert_eq!(client.current_penalty(&dob_token), 900);
+    }
+
+    #[test]
+    fn test_updater_handover() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let admin = Address::generate(&env);
+        let updater1 = Address::generate(&env);
+        let updater2 = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.initialize(&admin, &dob_token, &updater1, &10_000_000, &1000);
+        assert_eq!(client.updater(&dob_token), updater1);
+        assert_eq!(client.try_accept_updater(&dob_token), Err(Ok(Error::NoProposal)));
+
+        // Proposing alone does not move the role
+        client.propose_updater(&dob_token, &updater2, &None);
+        assert_eq!(client.updater(&dob_token), updater1);
+        assert_eq!(client.pending_updater(&dob_token).unwrap().proposed, updater2);
+
+        client.cancel_proposal(&dob_token);
+        assert_eq!(client.pending_updater(&dob_token), None);
+
+        // Expired proposals cannot be accepted
+        assert_eq!(
+            client.try_propose_updater(&dob_token, &updater2, &Some(1_000)),
+            Err(Ok(Error::ProposalExpired))
+        );
+        client.propose_updater(&dob_token, &updater2, &Some(2_000));
+        env.ledger().with_mut(|li| li.timestamp = 2_001);
+        assert_eq!(client.try_accept_updater(&dob_token), Err(Ok(Error::ProposalExpired)));
+
+        // Acceptance requires the new updater's auth
+        client.propose_updater(&dob_token, &updater2, &Some(3_000));
+        client.accept_updater(&dob_token);
+        assert_eq!(env.auths()[0].0, updater2);
+        assert_eq!(client.updater(&dob_token), updater2);
+        assert_eq!(client.pending_updater(&dob_token), None);
+
+        // The admin role is independent of the updater
+        assert_eq!(client.admin(), admin);
+    }
+
+    #[test]
+    fn test_admin_handover() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let admin1 = Address::generate(&env);
+        let admin2 = Address::generate(&env);
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.initialize(&admin1, &dob_token, &updater, &10_000_000, &1000);
+        assert_eq!(client.admin(), admin1);
+        assert_eq!(client.updater(&dob_token), updater);
+        assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoProposal)));
+
+        client.propose_admin(&admin2, &None);
+        assert_eq!(client.admin(), admin1);
+        assert_eq!(client.pending_admin().unwrap().proposed, admin2);
+
+        client.cancel_admin_proposal();
+        assert_eq!(client.pending_admin(), None);
+
+        assert_eq!(
+            client.try_propose_admin(&admin2, &Some(1_000)),
+            Err(Ok(Error::ProposalExpired))
+        );
+        client.propose_admin(&admin2, &Some(2_000));
+        env.ledger().with_mut(|li| li.timestamp = 2_001);
+        assert_eq!(client.try_accept_admin(), Err(Ok(Error::ProposalExpired)));
+
+        client.propose_admin(&admin2, &Some(3_000));
+        client.accept_admin();
+        assert_eq!(env.auths()[0].0, admin2);
+        assert_eq!(client.admin(), admin2);
+        assert_eq!(client.pending_admin(), None);
+
+        // Configuration now needs the new admin
+        client.set_guardian(&updater);
+        assert_eq!(env.auths()[0].0, admin2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid fair price")]
+    fn test_invalid_nav() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+        client.update(&dob_token, &0, &1000); // Should panic
+    }
+
+    #[test]
+    #[should_panic(expected = "Risk cannot exceed 100%")]
+    fn test_invalid_risk() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+        client.update(&dob_token, &10_000_000, &10001); // Should panic
+    }
+
+    #[test]
+    fn test_compute_fair_price_from_cashflows() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+
+        let dob_token = env.register_contract(None, MockDobToken);
+        let token_client = MockDobTokenClient::new(&env, &dob_token);
+
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+
+        // Two periods of 100 USDC, discounted at 10% per period
+        let cashflows = soroban_sdk::vec![
+            &env,
+            Cashflow { period: 1, amount: 100_0000000 },
+            Cashflow { period: 2, amount: 100_0000000 },
+        ];
+        client.set_cashflow_schedule(&dob_token, &cashflows, &1000);
+
+        // 100/1.1 + 100/1.21 = 173.5537 USDC before risk
+        // With 10% default risk: 173.5537 × 0.9 = 156.1983 USDC
+        assert_eq!(client.present_value(&dob_token), 1_561_983_390);
+
+        // No supply yet: price cannot be derived
+        assert_eq!(client.try_compute_fair_price(&dob_token), Err(Ok(Error::NoSupply)));
+
+        // 100 DOB outstanding → 1.561983 USDC per token
+        token_client.set_total_supply(&100_0000000);
+        let valuation = client.valuation(&dob_token);
+        assert_eq!(valuation.discounted_value, 1_735_537_100);
+        assert_eq!(valuation.fair_price, 15_619_833);
+
+        assert_eq!(client.compute_fair_price(&dob_token), 15_619_833);
+        assert_eq!(client.fair_price(&dob_token), 15_619_833);
+    }
+
+    #[test]
+    fn test_invalid_cashflow_schedule() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+
+        // Periods must be strictly increasing
+        let unordered = soroban_sdk::vec![
+            &env,
+            Cashflow { period: 2, amount: 100 },
+            Cashflow { period: 1, amount: 100 },
+        ];
+        assert_eq!(
+            client.try_set_cashflow_schedule(&dob_token, &unordered, &500),
+            Err(Ok(Error::InvalidSchedule))
+        );
+
+        // Periods far out would make discounting walk an unbounded number of steps
+        let distant = soroban_sdk::vec![&env, Cashflow { period: u32::MAX, amount: 100 }];
+        assert_eq!(
+            client.try_set_cashflow_schedule(&dob_token, &distant, &500),
+            Err(Ok(Error::InvalidSchedule))
+        );
+
+        // Negative cashflows are not allowed
+        let negative = soroban_sdk::vec![&env, Cashflow { period: 1, amount: -1 }];
+        assert_eq!(
+            client.try_set_cashflow_schedule(&dob_token, &negative, &500),
+            Err(Ok(Error::InvalidSchedule))
+        );
+
+        // Schedule missing entirely
+        assert_eq!(client.try_present_value(&dob_token), Err(Ok(Error::NotConfigured)));
+    }
+
+    #[test]
+    fn test_price_history_and_twap() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        env.ledger().with_mut(|li| li.timestamp = 1000);
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+
+        env.ledger().with_mut(|li| li.timestamp = 2000);
+        client.update(&dob_token, &12_000_000, &1000);
+
+        env.ledger().with_mut(|li| li.timestamp = 2500);
+        client.update(&dob_token, &11_000_000, &1000);
+
+        env.ledger().with_mut(|li| li.timestamp = 3000);
+
+        // 1.00 for 1000s, 1.20 for 500s, 1.10 for 500s
+        assert_eq!(client.twap(&dob_token, &2000), 10_750_000);
+        // Only the last 1000s: 1.20 and 1.10 for 500s each
+        assert_eq!(client.twap(&dob_token, &1000), 11_500_000);
+        // Window longer than history averages the covered span
+        assert_eq!(client.twap(&dob_token, &10_000), 10_750_000);
+        // Zero window is the spot price
+        assert_eq!(client.twap(&dob_token, &0), 11_000_000);
+
+        assert_eq!(client.price_at(&dob_token, &1000), 10_000_000);
+        assert_eq!(client.price_at(&dob_token, &2200), 12_000_000);
+        assert_eq!(client.price_at(&dob_token, &5000), 11_000_000);
+        assert_eq!(client.try_price_at(&dob_token, &999), Err(Ok(Error::NoHistory)));
+    }
+
+    #[test]
+    fn test_price_history_ring_buffer() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+
+        // Updates in the same ledger collapse into one observation
+        client.update(&dob_token, &10_500_000, &1000);
+        assert_eq!(client.price_history(&dob_token, &10).len(), 1);
+
+        for i in 1..=60u64 {
+            env.ledger().with_mut(|li| li.timestamp = i * 60);
+            client.update(&dob_token, &(10_000_000 + i as i128), &1000);
+        }
+
+        let history = client.price_history(&dob_token, &100);
+        assert_eq!(history.len(), HISTORY_CAPACITY);
+        assert_eq!(history.get(0).unwrap().fair_price, 10_000_060);
+        assert_eq!(history.get(HISTORY_CAPACITY - 1).unwrap().fair_price, 10_000_013);
+    }
+
+    #[test]
+    fn test_staleness() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 10_000;
+            li.sequence_number = 100;
+        });
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+
+        let last_updated = client.last_updated(&dob_token);
+        assert_eq!(last_updated.timestamp, 10_000);
+        assert_eq!(last_updated.ledger, 100);
+
+        // One hour later, a 1 day max age is still fresh
+        env.ledger().with_mut(|li| li.timestamp = 13_600);
+        assert_eq!(client.fair_price_checked(&dob_token, &86_400), 10_000_000);
+        assert_eq!(client.try_fair_price_checked(&dob_token, &3_599), Err(Ok(Error::Stale)));
+
+        // A new push refreshes the feed
+        env.ledger().with_mut(|li| li.sequence_number = 820);
+        client.update(&dob_token, &10_500_000, &1000);
+        assert_eq!(client.fair_price_checked(&dob_token, &0), 10_500_000);
+        assert_eq!(client.last_updated(&dob_token).ledger, 820);
+    }
+
+    #[test]
+    fn test_reporter_quorum_median() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let auditor = Address::generate(&env);
+        let rating_agency = Address::generate(&env);
+        let outsider = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&admin, &dob_token, &admin, &10_000_000, &1000);
+        assert_eq!(client.admin(), admin);
+
+        client.add_reporter(&operator);
+        client.add_reporter(&auditor);
+        client.add_reporter(&rating_agency);
+        assert_eq!(client.try_add_reporter(&auditor), Err(Ok(Error::AlreadyReporter)));
+
+        // The single updater cannot bypass the reporter set
+        assert_eq!(
+            client.try_update(&dob_token, &20_000_000, &1000),
+            Err(Ok(Error::ReportersActive))
+        );
+
+        // Quorum cannot exceed the reporter set
+        assert_eq!(client.try_set_quorum(&4), Err(Ok(Error::InvalidQuorum)));
+        client.set_quorum(&2);
+
+        assert_eq!(
+            client.try_submit_report(&outsider, &dob_token, &10_000_000, &1000),
+            Err(Ok(Error::NotReporter))
+        );
+
+        // First report does not publish
+        assert!(!client.submit_report(&operator, &dob_token, &11_000_000, &800));
+        assert_eq!(
+            client.try_submit_report(&operator, &dob_token, &11_000_000, &800),
+            Err(Ok(Error::AlreadyReported))
+        );
+        assert_eq!(client.fair_price(&dob_token), 10_000_000);
+
+        // Second report reaches quorum: median of two is their average
+        assert!(client.submit_report(&auditor, &dob_token, &11_400_000, &1000));
+        assert_eq!(client.fair_price(&dob_token), 11_200_000);
+        assert_eq!(client.default_risk(&dob_token), 900);
+        assert_eq!(client.current_round(&dob_token), 2);
+        assert_eq!(client.round_reports(&dob_token, &1).len(), 2);
+
+        // Three reports, one far off: median ignores the outlier
+        client.set_quorum(&3);
+        client.submit_report(&operator, &dob_token, &11_000_000, &800);
+        client.submit_report(&auditor, &dob_token, &11_100_000, &900);
+        client.submit_report(&rating_agency, &dob_token, &20_000_000, &900);
+        assert_eq!(client.fair_price(&dob_token), 11_100_000);
+        assert_eq!(client.default_risk(&dob_token), 900);
+
+        // Removing a reporter must keep quorum reachable
+        assert_eq!(client.try_remove_reporter(&auditor), Err(Ok(Error::InvalidQuorum)));
+        client.set_quorum(&2);
+        client.remove_reporter(&auditor);
+        assert_eq!(client.reporters().len(), 2);
+        assert_eq!(client.try_remove_reporter(&operator), Err(Ok(Error::InvalidQuorum)));
+
+        // Schedule-derived and signed prices cannot bypass the reporter set either
+        assert_eq!(client.try_compute_fair_price(&dob_token), Err(Ok(Error::ReportersActive)));
+        let report = SignedReport {
+            asset: dob_token.clone(),
+            fair_price: 20_000_000,
+            default_risk: 1000,
+            timestamp: 0,
+            nonce: 1,
+        };
+        assert_eq!(
+            client.try_submit_signed_report(
+                &report,
+                &BytesN::from_array(&env, &[0; 32]),
+                &BytesN::from_array(&env, &[0; 64])
+            ),
+            Err(Ok(Error::ReportersActive))
+        );
+
+        // Only disbanding the set hands pricing back to the updater
+        client.clear_reporters();
+        assert!(client.reporters().is_empty());
+        assert_eq!(client.quorum(), 1);
+        client.update(&dob_token, &20_000_000, &1000);
+        assert_eq!(client.fair_price(&dob_token), 20_000_000);
+    }
+
+    #[test]
+    fn test_round_timeout_and_removed_reporters() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let auditor = Address::generate(&env);
+        let rating_agency = Address::generate(&env);
+
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+        client.initialize(&admin, &dob_token, &admin, &10_000_000, &1000);
+        client.add_reporter(&operator);
+        client.add_reporter(&auditor);
+        client.add_reporter(&rating_agency);
+        client.set_quorum(&2);
+        client.set_round_timeout(&600);
+        assert_eq!(client.round_timeout(), 600);
+
+        // A lone report left past the timeout expires with its round
+        assert!(!client.submit_report(&operator, &dob_token, &30_000_000, &1000));
+        env.ledger().with_mut(|li| li.timestamp = 1_601);
+        assert!(!client.submit_report(&auditor, &dob_token, &11_000_000, &1000));
+        assert_eq!(client.current_round(&dob_token), 2);
+        assert_eq!(client.round_reports(&dob_token, &2).len(), 1);
+        assert_eq!(client.fair_price(&dob_token), 10_000_000);
+
+        // A report from a reporter removed mid-round no longer counts
+        client.remove_reporter(&auditor);
+        assert!(!client.submit_report(&operator, &dob_token, &12_000_000, &1000));
+        assert_eq!(client.round_reports(&dob_token, &2).len(), 1);
+
+        assert!(client.submit_report(&rating_agency, &dob_token, &12_200_000, &1000));
+        assert_eq!(client.fair_price(&dob_token), 12_100_000);
+        assert_eq!(client.current_round(&dob_token), 3);
+    }
+
+    #[test]
+    fn test_signed_reports() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let admin = Address::generate(&env);
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+
+        let sign = |report: &SignedReport| -> BytesN<64> {
+            let payload: std::vec::Vec<u8> = client.report_payload(report).iter().collect();
+            BytesN::from_array(&env, &signing_key.sign(&payload).to_bytes())
+        };
+
+        env.mock_all_auths();
+
+        env.ledger().with_mut(|li| li.timestamp = 5_000);
+        client.initialize(&admin, &dob_token, &admin, &10_000_000, &1000);
+
+        let report = SignedReport {
+            asset: dob_token.clone(),
+            fair_price: 11_500_000,
+            default_risk: 700,
+            timestamp: 4_900,
+            nonce: 1,
+        };
+
+        // Key must be registered first
+        assert_eq!(
+            client.try_submit_signed_report(&report, &public_key, &sign(&report)),
+            Err(Ok(Error::UnknownSigner))
+        );
+
+        client.add_signer(&public_key);
+        client.submit_signed_report(&report, &public_key, &sign(&report));
+        assert_eq!(client.fair_price(&dob_token), 11_500_000);
+        assert_eq!(client.default_risk(&dob_token), 700);
+        assert_eq!(client.signer_nonce(&public_key), 1);
+
+        // Staleness runs from when the valuation was produced, not when it was relayed
+        assert_eq!(client.last_updated(&dob_token).timestamp, 4_900);
+        assert_eq!(client.try_fair_price_checked(&dob_token, &50), Err(Ok(Error::Stale)));
+
+        // Relaying the same report again is a replay
+        assert_eq!(
+            client.try_submit_signed_report(&report, &public_key, &sign(&report)),
+            Err(Ok(Error::ReplayedNonce))
+        );
+
+        // Fresh nonce but an older valuation is rejected
+        let older = SignedReport { timestamp: 4_800, nonce: 2, ..report.clone() };
+        assert_eq!(
+            client.try_submit_signed_report(&older, &public_key, &sign(&older)),
+            Err(Ok(Error::InvalidTimestamp))
+        );
+
+        // Valuations from the future are rejected
+        let future = SignedReport { timestamp: 5_001, nonce: 2, ..report.clone() };
+        assert_eq!(
+            client.try_submit_signed_report(&future, &public_key, &sign(&future)),
+            Err(Ok(Error::InvalidTimestamp))
+        );
+
+        // Valuations older than the max report age are rejected
+        client.set_max_report_age(&600);
+        env.ledger().with_mut(|li| li.timestamp = 5_600);
+        let late = SignedReport { timestamp: 4_950, nonce: 2, ..report.clone() };
+        assert_eq!(
+            client.try_submit_signed_report(&late, &public_key, &sign(&late)),
+            Err(Ok(Error::Stale))
+        );
+        let fresh = SignedReport { timestamp: 5_000, nonce: 2, ..report.clone() };
+        client.submit_signed_report(&fresh, &public_key, &sign(&fresh));
+        assert_eq!(client.last_updated(&dob_token).timestamp, 5_000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_signed_report_bad_signature() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let admin = Address::generate(&env);
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+
+        env.mock_all_auths();
+
+        env.ledger().with_mut(|li| li.timestamp = 5_000);
+        client.initialize(&admin, &dob_token, &admin, &10_000_000, &1000);
+        client.add_signer(&public_key);
+
+        let report = SignedReport {
+            asset: dob_token.clone(),
+            fair_price: 11_500_000,
+            default_risk: 700,
+            timestamp: 4_900,
+            nonce: 1,
+        };
+
+        // Signature over a different price must not verify
+        let signature = signing_key.sign(b"fair_price=99");
+        client.submit_signed_report(
+            &report,
+            &public_key,
+            &BytesN::from_array(&env, &signature.to_bytes()),
+        );
+    }
+
+    #[test]
+    fn test_deviation_circuit_breaker() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let admin = Address::generate(&env);
+        let updater = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.initialize(&admin, &dob_token, &updater, &10_000_000, &1000);
+
+        // At most 20% price moves and 5 points of risk per update
+        client.set_deviation_limits(&DeviationLimits {
+            max_price_deviation_bps: 2000,
+            max_risk_deviation_bps: 500,
+        });
+
+        // Within limits: published immediately
+        client.update(&dob_token, &11_500_000, &1200);
+        assert_eq!(client.fair_price(&dob_token), 11_500_000);
+        assert_eq!(client.pending_update(&dob_token), None);
+
+        // Typo moves the price tenfold: held back
+        client.update(&dob_token, &115_000_000, &1200);
+        assert_eq!(client.fair_price(&dob_token), 11_500_000);
+        let pending = client.pending_update(&dob_token).unwrap();
+        assert_eq!(pending.fair_price, 115_000_000);
+        assert_eq!(pending.executable_at, 1_000 + 86_400);
+
+        // Admin rejects it
+        client.reject_pending_update(&dob_token);
+        assert_eq!(client.pending_update(&dob_token), None);
+        assert_eq!(client.try_approve_pending_update(&dob_token), Err(Ok(Error::NoPendingUpdate)));
+
+        // Large risk jump is held, then approved by the second approver
+        client.update(&dob_token, &11_500_000, &3000);
+        assert_eq!(client.default_risk(&dob_token), 1200);
+        client.approve_pending_update(&dob_token);
+        assert_eq!(client.default_risk(&dob_token), 3000);
+
+        // Held updates can also be executed by anyone after the timelock
+        client.update(&dob_token, &5_000_000, &3000);
+        assert_eq!(client.try_execute_pending_update(&dob_token), Err(Ok(Error::TimelockActive)));
+        env.ledger().with_mut(|li| li.timestamp = 1_000 + 86_400);
+        client.execute_pending_update(&dob_token);
+        assert_eq!(client.fair_price(&dob_token), 5_000_000);
+
+        // The approver must be a different key than the updater
+        client.update(&dob_token, &10_000_000, &3000);
+        client.set_asset_updater(&dob_token, &admin);
+        assert_eq!(client.try_approve_pending_update(&dob_token), Err(Ok(Error::Unauthorized)));
+        client.set_asset_updater(&dob_token, &updater);
+        client.approve_pending_update(&dob_token);
+        assert_eq!(client.fair_price(&dob_token), 10_000_000);
+
+        // Settlement prices pass the breaker too and only apply once published
+        client.set_settlement_price(&dob_token, &30_000_000);
+        assert_eq!(client.settlement_price(&dob_token), None);
+        assert!(client.pending_update(&dob_token).unwrap().settles);
+        client.approve_pending_update(&dob_token);
+        assert_eq!(client.settlement_price(&dob_token), Some(30_000_000));
+        assert_eq!(client.fair_price(&dob_token), 30_000_000);
+
+        // The settlement price is final
+        assert_eq!(
+            client.try_set_settlement_price(&dob_token, &31_000_000),
+            Err(Ok(Error::AlreadySettled))
+        );
+        assert_eq!(client.settlement_price(&dob_token), Some(30_000_000));
+    }
+
+    #[test]
+    fn test_penalty_curve() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let admin = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&admin, &dob_token, &admin, &10_000_000, &1000);
+
+        // Default curve matches 300 + risk/10 for every risk
+        for risk in [0u32, 1, 9, 1000, 1500, 3333, 10000] {
+            assert_eq!(client.calculate_penalty(&dob_token, &risk), 300 + risk / 10);
+        }
+
+        // Flat 2% up to 10% risk, steeper to 20% at 50% risk, capped beyond
+        let curve = soroban_sdk::vec![
+            &env,
+            PenaltyPoint { risk_bps: 1000, penalty_bps: 200 },
+            PenaltyPoint { risk_bps: 5000, penalty_bps: 2000 },
+        ];
+        client.set_penalty_curve(&dob_token, &curve);
+
+        assert_eq!(client.calculate_penalty(&dob_token, &0), 200);
+        assert_eq!(client.calculate_penalty(&dob_token, &1000), 200);
+        assert_eq!(client.calculate_penalty(&dob_token, &3000), 1100);
+        assert_eq!(client.calculate_penalty(&dob_token, &5000), 2000);
+        assert_eq!(client.calculate_penalty(&dob_token, &9000), 2000);
+
+        // Breakpoints must be increasing and penalties at most 50%
+        let unordered = soroban_sdk::vec![
+            &env,
+            PenaltyPoint { risk_bps: 5000, penalty_bps: 200 },
+            PenaltyPoint { risk_bps: 5000, penalty_bps: 900 },
+        ];
+        assert_eq!(
+            client.try_set_penalty_curve(&dob_token, &unordered),
+            Err(Ok(Error::InvalidPenaltyCurve))
+        );
+
+        let excessive = soroban_sdk::vec![&env, PenaltyPoint { risk_bps: 0, penalty_bps: 5001 }];
+        assert_eq!(
+            client.try_set_penalty_curve(&dob_token, &excessive),
+            Err(Ok(Error::InvalidPenaltyCurve))
+        );
+
+        let empty: Vec<PenaltyPoint> = Vec::new(&env);
+        assert_eq!(client.try_set_penalty_curve(&dob_token, &empty), Err(Ok(Error::InvalidPenaltyCurve)));
+    }
+
+    #[test]
+    fn test_sep40_interface() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let dob_token = Address::generate(&env);
+        let usdc = Address::generate(&env);
+        let dob = Asset::Stellar(dob_token.clone());
+
+        env.mock_all_auths();
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.initialize(&admin, &dob_token, &admin, &10_000_000, &1000);
+
+        assert_eq!(client.decimals(), 7);
+        assert_eq!(client.base(), Asset::Other(Symbol::new(&env, "USDC")));
+        client.set_base_asset(&Asset::Stellar(usdc.clone()));
+        assert_eq!(client.base(), Asset::Stellar(usdc));
+
+        // The initial series is listed from the start
+        assert_eq!(client.assets(), soroban_sdk::vec![&env, dob.clone()]);
+
+        env.ledger().with_mut(|li| li.timestamp = 2_000);
+        client.update(&dob_token, &11_000_000, &1000);
+
+        assert_eq!(
+            client.lastprice(&dob),
+            Some(PriceData { price: 11_000_000, timestamp: 2_000 })
+        );
+        assert_eq!(
+            client.price(&dob, &1_500),
+            Some(PriceData { price: 10_000_000, timestamp: 1_000 })
+        );
+        assert_eq!(client.price(&dob, &500), None);
+
+        let prices = client.prices(&dob, &5).unwrap();
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices.get(0).unwrap().price, 11_000_000);
+
+        // Unknown assets have no price
+        let other = Asset::Other(Symbol::new(&env, "XLM"));
+        assert_eq!(client.lastprice(&other), None);
+        assert_eq!(client.prices(&other, &5), None);
+    }
+
+    #[test]
+    fn test_asset_registry() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let primary_updater = Address::generate(&env);
+        let solar_updater = Address::generate(&env);
+        let wind_updater = Address::generate(&env);
+        let primary = Address::generate(&env);
+        let solar_2030 = Address::generate(&env);
+        let wind_2032 = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.initialize(&admin, &primary, &primary_updater, &10_000_000, &1000);
+
+        client.register_asset(&solar_2030, &solar_updater, &9_500_000, &800);
+        client.register_asset(&wind_2032, &wind_updater, &10_200_000, &1500);
+        assert_eq!(
+            client.try_register_asset(&solar_2030, &solar_updater, &9_500_000, &800),
+            Err(Ok(Error::AssetExists))
+        );
+        assert_eq!(
+            client.try_register_asset(&primary, &solar_updater, &9_500_000, &800),
+            Err(Ok(Error::AssetExists))
+        );
+
+        assert_eq!(client.asset_count(), 3);
+        assert_eq!(client.registered_assets_page(&2, &10), soroban_sdk::vec![&env, wind_2032.clone()]);
+        assert_eq!(client.registered_assets_page(&5, &10).len(), 0);
+
+        // Series prices are independent of each other
+        env.ledger().with_mut(|li| li.timestamp = 2_000);
+        client.update(&solar_2030, &9_800_000, &700);
+        assert_eq!(env.auths()[0].0, solar_updater);
+        assert_eq!(client.fair_price(&solar_2030), 9_800_000);
+        assert_eq!(client.default_risk(&solar_2030), 700);
+        assert_eq!(client.fair_price(&wind_2032), 10_200_000);
+        assert_eq!(client.fair_price(&primary), 10_000_000);
+
+        // Staleness and history are tracked per series
+        env.ledger().with_mut(|li| li.timestamp = 3_000);
+        assert_eq!(client.fair_price_checked(&solar_2030, &1_000), 9_800_000);
+        assert_eq!(
+            client.try_fair_price_checked(&wind_2032, &1_000),
+            Err(Ok(Error::Stale))
+        );
+        assert_eq!(client.twap(&solar_2030, &2_000), 9_650_000);
+        assert_eq!(client.twap(&wind_2032, &2_000), 10_200_000);
+
+        // The circuit breaker holds updates per series
+        client.set_deviation_limits(&DeviationLimits {
+            max_price_deviation_bps: 1000,
+            max_risk_deviation_bps: 1000,
+        });
+        client.update(&wind_2032, &20_000_000, &1500);
+        assert_eq!(client.fair_price(&wind_2032), 10_200_000);
+        assert_eq!(client.pending_update(&wind_2032).unwrap().fair_price, 20_000_000);
+        assert_eq!(client.pending_update(&solar_2030), None);
+
+        // Credit state and penalty curve are per series
+        client.set_recovery_price(&wind_2032, &3_000_000);
+        client.transition_credit_state(&wind_2032, &CreditState::Watchlist, &1);
+        client.transition_credit_state(&wind_2032, &CreditState::Delinquent, &2);
+        client.transition_credit_state(&wind_2032, &CreditState::Defaulted, &3);
+        assert!(!client.issuance_open(&wind_2032));
+        assert!(client.issuance_open(&solar_2030));
+        assert_eq!(client.recovery_redemption_price(&wind_2032), Some(3_000_000));
+        assert_eq!(client.recovery_redemption_price(&solar_2030), None);
+
+        client.set_penalty_curve(
+            &solar_2030,
+            &soroban_sdk::vec![&env, PenaltyPoint { risk_bps: 0, penalty_bps: 100 }],
+        );
+        assert_eq!(client.calculate_penalty(&solar_2030, &5000), 100);
+        assert_eq!(client.calculate_penalty(&wind_2032, &5000), 800);
+
+        // Registered series are listed through SEP-40 too
+        assert_eq!(client.assets().len(), 3);
+        assert_eq!(
+            client.lastprice(&Asset::Stellar(solar_2030.clone())),
+            Some(PriceData { price: 9_800_000, timestamp: 2_000 })
+        );
+        assert_eq!(client.prices(&Asset::Stellar(solar_2030.clone()), &5).unwrap().len(), 2);
+
+        client.set_asset_updater(&solar_2030, &wind_updater);
+        assert_eq!(client.series(&solar_2030).updater, wind_updater);
+
+        client.remove_asset(&wind_2032);
+        assert_eq!(client.try_fair_price(&wind_2032), Err(Ok(Error::AssetNotFound)));
+        assert_eq!(client.lastprice(&Asset::Stellar(wind_2032.clone())), None);
+        assert_eq!(client.asset_count(), 2);
+
+        // Its held-back update is dropped with it instead of publishing to a missing series
+        assert_eq!(client.pending_update(&wind_2032), None);
+        env.ledger().with_mut(|li| li.timestamp = 100_000);
+        assert_eq!(
+            client.try_execute_pending_update(&wind_2032),
+            Err(Ok(Error::NoPendingUpdate))
+        );
+    }
+
+    #[test]
+    fn test_remove_asset_clears_open_round() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let auditor = Address::generate(&env);
+        let primary = Address::generate(&env);
+        let solar_2030 = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&admin, &primary, &admin, &10_000_000, &1000);
+        client.register_asset(&solar_2030, &admin, &9_500_000, &800);
+        client.add_reporter(&operator);
+        client.add_reporter(&auditor);
+        client.set_quorum(&2);
+
+        assert!(!client.submit_report(&operator, &solar_2030, &30_000_000, &800));
+        client.remove_asset(&solar_2030);
+        assert_eq!(client.round_reports(&solar_2030, &1).len(), 0);
+
+        // Re-registered, the series opens a fresh round without the old report
+        client.register_asset(&solar_2030, &admin, &9_500_000, &800);
+        assert_eq!(client.current_round(&solar_2030), 2);
+        assert!(!client.submit_report(&auditor, &solar_2030, &9_600_000, &800));
+        assert!(client.submit_report(&operator, &solar_2030, &9_800_000, &800));
+        assert_eq!(client.fair_price(&solar_2030), 9_700_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid fair price")]
+    fn test_initialize_rejects_zero_price() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+        let admin = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&admin, &dob_token, &admin, &0, &1000);
+    }
+
+    #[test]
+    fn test_freeze() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let guardian = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let dob_token = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&admin, &dob_token, &admin, &10_000_000, &1000);
+        client.set_guardian(&guardian);
+
+        assert_eq!(client.try_freeze(&stranger), Err(Ok(Error::Unauthorized)));
+
+        client.freeze(&guardian);
+        assert!(client.is_frozen());
+        assert_eq!(client.try_fair_price(&dob_token), Err(Ok(Error::Paused)));
+        assert_eq!(client.try_default_risk(&dob_token), Err(Ok(Error::Paused)));
+        assert_eq!(client.try_fair_price_checked(&dob_token, &3_600), Err(Ok(Error::Paused)));
+        assert_eq!(client.try_twap(&dob_token, &3_600), Err(Ok(Error::Paused)));
+        assert_eq!(client.lastprice(&Asset::Stellar(dob_token.clone())), None);
+
+        // The updater can still correct the price while frozen
+        client.update(&dob_token, &9_000_000, &1500);
+
+        client.unfreeze();
+        assert!(!client.is_frozen());
+        assert_eq!(client.fair_price(&dob_token), 9_000_000);
+        assert!(client.lastprice(&Asset::Stellar(dob_token)).is_some());
+    }
+
+    #[test]
+    fn test_credit_state_machine() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let admin = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.initialize(&admin, &dob_token, &admin, &10_000_000, &1000);
+        assert_eq!(client.credit_state(&dob_token), CreditState::Performing);
+        assert!(client.issuance_open(&dob_token));
+
+        // Performing cannot jump straight to Defaulted
+        assert_eq!(
+            client.try_transition_credit_state(&dob_token, &CreditState::Defaulted, &1),
+            Err(Ok(Error::InvalidTransition))
+        );
+
+        env.ledger().with_mut(|li| li.timestamp = 2_000);
+        client.transition_credit_state(&dob_token, &CreditState::Watchlist, &10);
+        client.transition_credit_state(&dob_token, &CreditState::Delinquent, &20);
+        assert!(client.issuance_open(&dob_token));
+        assert_eq!(client.recovery_redemption_price(&dob_token), None);
+
+        // Defaulting needs a recovery price to settle redemptions at
+        assert_eq!(
+            client.try_transition_credit_state(&dob_token, &CreditState::Defaulted, &30),
+            Err(Ok(Error::NotConfigured))
+        );
+        client.set_recovery_price(&dob_token, &4_000_000);
+
+        env.ledger().with_mut(|li| li.timestamp = 3_000);
+        client.transition_credit_state(&dob_token, &CreditState::Defaulted, &30);
+        assert_eq!(
+            client.credit_status(&dob_token),
+            CreditStatus {
+                state: CreditState::Defaulted,
+                reason_code: 30,
+                since: 3_000
+            }
+        );
+        assert!(!client.issuance_open(&dob_token));
+        assert_eq!(client.recovery_redemption_price(&dob_token), Some(4_000_000));
+
+        client.transition_credit_state(&dob_token, &CreditState::Recovered, &40);
+        client.transition_credit_state(&dob_token, &CreditState::Matured, &50);
+        assert_eq!(client.recovery_redemption_price(&dob_token), None);
+        assert_eq!(
+            client.try_transition_credit_state(&dob_token, &CreditState::Performing, &0),
+            Err(Ok(Error::InvalidTransition))
+        );
+
+        let history = client.credit_history(&dob_token);
+        assert_eq!(history.len(), 5);
+        assert_eq!(history.get_unchecked(0).state, CreditState::Performing);
+        assert_eq!(history.get_unchecked(0).since, 1_000);
+        assert_eq!(history.get_unchecked(3).state, CreditState::Defaulted);
+    }
+
+    #[test]
+    fn test_realized_cashflows() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+        let evidence = BytesN::from_array(&env, &[7u8; 32]);
+
+        env.mock_all_auths();
+
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+
+        let mut cashflows = Vec::new(&env);
+        for period in 1..=4u32 {
+            cashflows.push_back(Cashflow {
+                period,
+                amount: 100_0000000,
+            });
+        }
+        client.set_cashflow_schedule(&dob_token, &cashflows, &100);
+
+        // Two short periods in a row (more than 10% below plan) raise risk by 5%
+        client.set_underperformance_policy(&UnderperformancePolicy {
+            shortfall_threshold_bps: 1000,
+            consecutive_periods: 2,
+            risk_step_bps: 500,
+        });
+
+        assert_eq!(
+            client.try_report_realized_cashflow(&dob_token, &9, &100_0000000, &evidence),
+            Err(Ok(Error::UnknownPeriod))
+        );
+
+        client.report_realized_cashflow(&dob_token, &1, &110_0000000, &evidence);
+        assert_eq!(
+            client.try_report_realized_cashflow(&dob_token, &1, &110_0000000, &evidence),
+            Err(Ok(Error::AlreadyReported))
+        );
+
+        client.report_realized_cashflow(&dob_token, &2, &80_0000000, &evidence);
+        assert_eq!(client.default_risk(&dob_token), 1000);
+
+        client.report_realized_cashflow(&dob_token, &3, &70_0000000, &evidence);
+        assert_eq!(client.default_risk(&dob_token), 1500);
+
+        let performance = client.cashflow_performance(&dob_token);
+        assert_eq!(performance.periods_reported, 3);
+        assert_eq!(performance.projected_total, 300_0000000);
+        assert_eq!(performance.realized_total, 260_0000000);
+        assert_eq!(performance.variance, -40_0000000);
+        assert_eq!(performance.variance_bps, -1333);
+        assert_eq!(performance.consecutive_shortfalls, 2);
+
+        // Back on plan resets the run
+        client.report_realized_cashflow(&dob_token, &4, &95_0000000, &evidence);
+        assert_eq!(client.cashflow_performance(&dob_token).consecutive_shortfalls, 0);
+        assert_eq!(client.default_risk(&dob_token), 1500);
+        assert_eq!(client.realized_cashflow(&dob_token, &3).unwrap().amount, 70_0000000);
+    }
+
+    #[test]
+    fn test_shortfalls_follow_period_order() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let updater = Address::generate(&env);
+        let evidence = BytesN::from_array(&env, &[7u8; 32]);
+
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+        client.initialize(&updater, &dob_token, &updater, &10_000_000, &1000);
+
+        let mut cashflows = Vec::new(&env);
+        for period in 1..=4u32 {
+            cashflows.push_back(Cashflow {
+                period,
+                amount: 100_0000000,
+            });
+        }
+        client.set_cashflow_schedule(&dob_token, &cashflows, &100);
+        client.set_underperformance_policy(&UnderperformancePolicy {
+            shortfall_threshold_bps: 1000,
+            consecutive_periods: 2,
+            risk_step_bps: 500,
+        });
+
+        // Periods 3 and 1 are short but not adjacent: no run of two yet
+        env.ledger().with_mut(|li| li.timestamp = 5_000);
+        client.report_realized_cashflow(&dob_token, &3, &70_0000000, &evidence);
+        client.report_realized_cashflow(&dob_token, &1, &80_0000000, &evidence);
+        assert_eq!(client.cashflow_performance(&dob_token).consecutive_shortfalls, 1);
+        assert_eq!(client.default_risk(&dob_token), 1000);
+
+        // Filling in period 2 completes a run of three
+        client.report_realized_cashflow(&dob_token, &2, &80_0000000, &evidence);
+        assert_eq!(client.cashflow_performance(&dob_token).consecutive_shortfalls, 3);
+        assert_eq!(client.default_risk(&dob_token), 1500);
+
+        // Raising risk does not refresh the price's last update
+        assert_eq!(client.last_updated(&dob_token).timestamp, 1_000);
+        assert_eq!(client.try_fair_price_checked(&dob_token, &3_600), Err(Ok(Error::Stale)));
+
+        // A new schedule starts the realized record over
+        client.set_cashflow_schedule(&dob_token, &cashflows, &100);
+        assert_eq!(client.cashflow_performance(&dob_token).periods_reported, 0);
+        assert_eq!(client.realized_cashflow(&dob_token, &2), None);
+        client.report_realized_cashflow(&dob_token, &2, &100_0000000, &evidence);
+        assert_eq!(client.cashflow_performance(&dob_token).periods_reported, 1);
+    }
+
+    #[test]
+    fn test_apply_amortization() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = env.register_contract(None, MockDobToken);
+        let token_client = MockDobTokenClient::new(&env, &dob_token);
+
+        let admin = Address::generate(&env);
+        let amortizer = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&admin, &dob_token, &admin, &10_000_000, &1000);
+
+        let mut cashflows = Vec::new(&env);
+        cashflows.push_back(Cashflow { period: 1, amount: 100_0000000 });
+        cashflows.push_back(Cashflow { period: 2, amount: 200_0000000 });
+        client.set_cashflow_schedule(&dob_token, &cashflows, &100);
+        token_client.set_total_supply(&100_0000000);
+        let price = client.valuation(&dob_token).fair_price;
+
+        assert_eq!(client.try_apply_amortization(&dob_token, &10_0000000), Err(Ok(Error::NotConfigured)));
+
+        client.set_amortizer(&dob_token, &amortizer);
+
+        // A first batch retires 4% of supply: cashflows follow, not the full period factor
+        token_client.set_total_supply(&96_0000000);
+        client.apply_amortization(&dob_token, &4_0000000);
+        assert_eq!(env.auths()[0].0, amortizer);
+
+        let amortized = client.cashflow_schedule(&dob_token);
+        assert_eq!(amortized.get_unchecked(0).amount, 96_0000000);
+        assert_eq!(amortized.get_unchecked(1).amount, 192_0000000);
+        assert_eq!(client.valuation(&dob_token).fair_price, price);
+        assert_eq!(client.fair_price(&dob_token), price);
+
+        // The rest of the period: 10% of the original supply retired in total
+        token_client.set_total_supply(&90_0000000);
+        client.apply_amortization(&dob_token, &6_0000000);
+
+        let amortized = client.cashflow_schedule(&dob_token);
+        assert_eq!(amortized.get_unchecked(0).amount, 90_0000000);
+        assert_eq!(amortized.get_unchecked(1).amount, 180_0000000);
+        assert_eq!(client.fair_price(&dob_token), price);
+
+        // With a reporter set the schedule still scales but no price is published
+        client.add_reporter(&admin);
+        let last_updated = client.last_updated(&dob_token);
+        env.ledger().with_mut(|li| li.timestamp += 60);
+        token_client.set_total_supply(&45_0000000);
+        client.apply_amortization(&dob_token, &45_0000000);
+        assert_eq!(client.cashflow_schedule(&dob_token).get_unchecked(0).amount, 45_0000000);
+        assert_eq!(client.last_updated(&dob_token), last_updated);
+    }
+
+    #[test]
+    fn test_ttl_config() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobOracle);
+        let client = DobOracleClient::new(&env, &contract_id);
+        let dob_token = Address::generate(&env);
+
+        let admin = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&admin, &dob_token, &admin, &10_000_000, &1000);
+
+        let config = TtlConfig {
+            instance_threshold: 10_000,
+            instance_extend_to: 20_000,
+            persistent_threshold: 10_000,
+            persistent_extend_to: 20_000,
+        };
+        client.set_ttl_config(&config);
+        assert_eq!(client.ttl_config(), config);
+
+        let instance_ttl = || env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+
+        // Getters the markets poll keep the instance alive
+        env.ledger().with_mut(|li| li.sequence_number += 15_000);
+        assert_eq!(instance_ttl(), 5_000);
+        client.credit_status(&dob_token);
+        assert_eq!(instance_ttl(), 20_000);
+
+        env.ledger().with_mut(|li| li.sequence_number += 15_000);
+        client.fair_price_checked(&dob_token, &u64::MAX);
+        assert_eq!(instance_ttl(), 20_000);
 
-        client.initialize(&updater, &10_000_000, &1000);
-        client.update(&10_000_000, &10001); // Should panic
+        let invalid = TtlConfig {
+            instance_threshold: 30_000,
+            ..config
+        };
+        assert_eq!(client.try_set_ttl_config(&invalid), Err(Ok(Error::InvalidTtl)));
     }
 }
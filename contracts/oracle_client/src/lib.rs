@@ -0,0 +1,56 @@
+#![no_std]
+
+//! Checked DobOracle reads shared by the contracts that price against it
+//!
+//! The oracle's stale and frozen errors come back as `OracleFailure` so each
+//! caller can map them onto its own error codes; any other oracle failure panics
+
+use soroban_sdk::{Address, Env, IntoVal, InvokeError, Symbol};
+
+const ORACLE_ERROR_STALE: u32 = 6; // DobOracle Error::Stale
+const ORACLE_ERROR_PAUSED: u32 = 21; // DobOracle Error::Paused
+
+/// Why the oracle refused a checked read
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OracleFailure {
+    Stale,  // Price older than the caller's heartbeat
+    Paused, // Oracle frozen by its guardian
+}
+
+/// Read the fair price of `asset`, failing if it is older than `max_age`
+/// seconds or the oracle is frozen
+pub fn fair_price_checked(
+    env: &Env,
+    oracle: &Address,
+    asset: &Address,
+    max_age: u64,
+) -> Result<i128, OracleFailure> {
+    match env.try_invoke_contract::<i128, InvokeError>(
+        oracle,
+        &Symbol::new(env, "fair_price_checked"),
+        (asset.clone(), max_age).into_val(env),
+    ) {
+        Ok(Ok(price)) => Ok(price),
+        Err(Ok(InvokeError::Contract(ORACLE_ERROR_STALE))) => Err(OracleFailure::Stale),
+        Err(Ok(InvokeError::Contract(ORACLE_ERROR_PAUSED))) => Err(OracleFailure::Paused),
+        _ => panic!("Oracle call failed"),
+    }
+}
+
+/// Recovery price an impaired series settles at, `None` while it is
+/// performing; fails if the oracle is frozen
+pub fn recovery_redemption_price(
+    env: &Env,
+    oracle: &Address,
+    asset: &Address,
+) -> Result<Option<i128>, OracleFailure> {
+    match env.try_invoke_contract::<Option<i128>, InvokeError>(
+        oracle,
+        &Symbol::new(env, "recovery_redemption_price"),
+        (asset.clone(),).into_val(env),
+    ) {
+        Ok(Ok(price)) => Ok(price),
+        Err(Ok(InvokeError::Contract(ORACLE_ERROR_PAUSED))) => Err(OracleFailure::Paused),
+        _ => panic!("Oracle call failed"),
+    }
+}
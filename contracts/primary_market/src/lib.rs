@@ -1,6 +1,10 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, contracterror, token, Address, Env, IntoVal, Symbol, Vec};
+use dob_oracle_client::OracleFailure;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, contracterror, token, Address, BytesN, Env, IntoVal,
+    Symbol, Vec,
+};
 
 /// Storage keys for the contract
 #[contracttype]
@@ -10,8 +14,21 @@ pub enum DataKey {
     UsdcToken,   // USDC token contract address
     Oracle,      // Oracle contract address
     Operator,    // Operator receiving revenues
+    Admin,       // Admin upgrading the contract, separate from the operator
     TotalBought, // Total USDC spent on buys
     TotalSold,   // Total DOB sold
+    TwapWindow,  // Seconds of oracle history averaged for redemptions and mints
+    Heartbeat,   // Max age in seconds of an oracle price we trade against
+    PendingOperator, // OperatorProposal awaiting acceptance
+    PendingAdmin,    // AdminProposal awaiting acceptance
+    MaturityGrace, // Seconds after maturity before unredeemed USDC can be swept
+    AmortizationSchedule, // Vec<AmortizationPeriod> of principal repayments
+    NextAmortization,     // Index of the next period to open
+    Amortized(u32, Address), // Holder already amortized for a period
+    AmortizationSnapshot(u32), // DOB token snapshot a period's holdings are read at
+    AmortizationPrice(u32),    // USDC paid per retired DOB, fixed from the oracle at opening
+    TtlConfig,               // Storage TTL thresholds, defaults when unset
+    SchemaVersion,           // Storage layout version applied by migrate()
 }
 
 /// Buy event data
@@ -35,12 +52,56 @@ pub struct SellEvent {
 
 /// Redemption quote
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RedemptionQuote {
     pub usdc_out: i128,
     pub penalty_bps: u32,
 }
 
+/// Pending operator handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// Pending admin handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// One principal repayment of an amortizing series
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AmortizationPeriod {
+    pub due_at: u64,     // Timestamp the period can be opened from
+    pub factor_bps: u32, // Share of each holder's tokens retired, up to MAX_AMORTIZATION_BPS
+}
+
+/// Holder amortization event
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AmortizedEvent {
+    pub holder: Address,
+    pub dob_retired: i128,
+    pub usdc_out: i128,
+}
+
+/// Ledgers before expiry at which entries are extended, and the TTL they are
+/// extended to; persistent values apply to amortization records
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    pub instance_threshold: u32,
+    pub instance_extend_to: u32,
+    pub persistent_threshold: u32,
+    pub persistent_extend_to: u32,
+}
+
 /// Errors that can be returned by the contract
 #[contracterror]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -48,11 +109,47 @@ pub enum Error {
     InsufficientLiquidity = 1,
     InvalidAmount = 2,
     TransferFailed = 3,
+    StalePrice = 4,
+    OraclePaused = 5,
+    NoProposal = 6,
+    ProposalExpired = 7,
+    IssuanceClosed = 8,
+    Matured = 9,
+    NotMatured = 10,
+    NotSettled = 11,
+    GracePeriodActive = 12,
+    InvalidSchedule = 13,
+    AmortizationNotDue = 14,
+    PeriodNotOpen = 15,
+    AlreadyMigrated = 16,
+    ReserveHolder = 17,
+    InvalidTtl = 18,
+    InvalidGrace = 19,
+}
+
+impl From<OracleFailure> for Error {
+    fn from(failure: OracleFailure) -> Self {
+        match failure {
+            OracleFailure::Stale => Error::StalePrice,
+            OracleFailure::Paused => Error::OraclePaused,
+        }
+    }
 }
 
 // Constants
 const OPERATOR_SHARE: u32 = 99; // 99% to operator on buys
 const BPS: u32 = 10000; // Basis points denominator
+const DEFAULT_TWAP_WINDOW: u64 = 3600; // 1 hour of oracle history
+const DEFAULT_HEARTBEAT: u64 = 86_400; // Reject oracle prices older than 1 day
+const DEFAULT_MATURITY_GRACE: u64 = 180 * 86_400; // 180 days to redeem after maturity
+const MIN_MATURITY_GRACE: u64 = 30 * 86_400; // Holders always get at least 30 days
+const MAX_AMORTIZATION_BPS: u32 = 5000; // At most half of each holding retired per period
+const DAY_IN_LEDGERS: u32 = 17280; // ~5s per ledger
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const SCHEMA_VERSION: u32 = 1; // Bump together with a migrate() step
 
 #[contract]
 pub struct DobPrimaryMarket;
@@ -60,8 +157,10 @@ pub struct DobPrimaryMarket;
 #[contractimpl]
 impl DobPrimaryMarket {
     /// Initialize the primary market contract
+    /// The admin upgrades the market; the operator receives proceeds and runs redemptions
     pub fn initialize(
         env: Env,
+        admin: Address,
         dob_token: Address,
         usdc_token: Address,
         oracle: Address,
@@ -71,20 +170,26 @@ impl DobPrimaryMarket {
             panic!("Already initialized");
         }
 
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::DobToken, &dob_token);
         env.storage().instance().set(&DataKey::UsdcToken, &usdc_token);
         env.storage().instance().set(&DataKey::Oracle, &oracle);
         env.storage().instance().set(&DataKey::Operator, &operator);
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
         env.storage().instance().set(&DataKey::TotalBought, &0i128);
         env.storage().instance().set(&DataKey::TotalSold, &0i128);
     }
 
     /// Buy DOB tokens with USDC (Primary Market)
     /// 99% of USDC goes to operator, 1% fee
-    /// DOB tokens minted to buyer at NAV rate
+    /// DOB tokens minted to buyer at the higher of spot and TWAP NAV
     pub fn buy(env: Env, buyer: Address, usdc_amount: i128) -> Result<i128, Error> {
         buyer.require_auth();
 
+        Self::extend_instance(&env);
+
         if usdc_amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -110,8 +215,14 @@ impl DobPrimaryMarket {
             .get(&DataKey::Operator)
             .expect("Operator not set");
 
-        // Get current NAV from oracle
-        let nav: i128 = env.invoke_contract(&oracle, &Symbol::new(&env, "nav"), soroban_sdk::vec![&env]);
+        if Self::maturity(&env, &dob_token).is_some_and(|maturity| env.ledger().timestamp() >= maturity) {
+            return Err(Error::Matured);
+        }
+
+        // Mint at the higher of the spot NAV and its TWAP, rejecting a stale feed,
+        // so a single low push cannot be used to mint cheaply
+        let nav = Self::mint_price(&env, &oracle, &dob_token)?;
+        Self::ensure_issuance_open(&env, &oracle, &dob_token)?;
 
         // Transfer USDC from buyer to contract
         let usdc_client = token::Client::new(&env, &usdc_token);
@@ -127,7 +238,7 @@ impl DobPrimaryMarket {
         let dob_amount = (operator_amount * 10_000_000) / nav;
 
         // Mint DOB tokens to buyer
-        let mint_args: Vec<soroban_sdk::Val> = (buyer.clone(), dob_amount).into_val(&env);
+        let mint_args: Vec<soroban_sdk::Val> = (env.current_contract_address(), buyer.clone(), dob_amount).into_val(&env);
         let _: () = env.invoke_contract(
             &dob_token,
             &Symbol::new(&env, "mint"),
@@ -159,10 +270,12 @@ impl DobPrimaryMarket {
 
     /// Sell DOB tokens for USDC (Secondary Market)
     /// USDC returned = DOB × NAV × (1 - penalty)
-    /// Penalty based on default risk: 3% base + risk/10
+    /// Penalty from the oracle's penalty curve for the current default risk
     pub fn sell(env: Env, seller: Address, dob_amount: i128) -> Result<i128, Error> {
         seller.require_auth();
 
+        Self::extend_instance(&env);
+
         if dob_amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -179,7 +292,7 @@ impl DobPrimaryMarket {
             .expect("USDC token not set");
 
         // Calculate redemption
-        let quote = Self::quote_redemption(env.clone(), dob_amount);
+        let quote = Self::quote_redemption(env.clone(), dob_amount)?;
 
         // Check contract has enough USDC
         let usdc_client = token::Client::new(&env, &usdc_token);
@@ -190,10 +303,10 @@ impl DobPrimaryMarket {
         }
 
         // Burn DOB tokens from seller
-        let burn_args: Vec<soroban_sdk::Val> = (seller.clone(), dob_amount).into_val(&env);
+        let burn_args: Vec<soroban_sdk::Val> = (env.current_contract_address(), seller.clone(), dob_amount).into_val(&env);
         let _: () = env.invoke_contract(
             &dob_token,
-            &Symbol::new(&env, "burn"),
+            &Symbol::new(&env, "minter_burn"),
             burn_args,
         );
 
@@ -220,122 +333,1184 @@ impl DobPrimaryMarket {
         Ok(quote.usdc_out)
     }
 
-    /// Get quote for selling DOB tokens
-    /// Returns expected USDC output and penalty in basis points
-    pub fn quote_redemption(env: Env, dob_amount: i128) -> RedemptionQuote {
+    /// Redeem matured DOB tokens at the oracle's final settlement price, without penalty
+    /// An impaired series pays its recovery price instead
+    pub fn redeem_at_maturity(env: Env, holder: Address, dob_amount: i128) -> Result<i128, Error> {
+        holder.require_auth();
+
+        Self::extend_instance(&env);
+
+        if dob_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let dob_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DobToken)
+            .expect("DOB token not set");
+        let usdc_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::UsdcToken)
+            .expect("USDC token not set");
         let oracle: Address = env
             .storage()
             .instance()
             .get(&DataKey::Oracle)
             .expect("Oracle not set");
 
-        // Get NAV and default risk from oracle
-        let nav: i128 = env.invoke_contract(&oracle, &Symbol::new(&env, "nav"), soroban_sdk::vec![&env]);
-        let risk: u32 = env.invoke_contract(&oracle, &Symbol::new(&env, "default_risk"), soroban_sdk::vec![&env]);
+        match Self::maturity(&env, &dob_token) {
+            Some(maturity) if env.ledger().timestamp() >= maturity => {}
+            _ => return Err(Error::NotMatured),
+        }
 
-        // Calculate penalty: 3% base + risk/10
-        let penalty_bps = 300 + (risk / 10);
-        // Cap at 50%
-        let penalty_bps = if penalty_bps > 5000 { 5000 } else { penalty_bps };
+        let settlement_price = Self::settlement_price(&env, &oracle, &dob_token)?;
 
-        // USDC out = DOB × NAV × (1 - penalty)
-        // DOB is 7 decimals, NAV is 7 decimals
-        // Result should be 7 decimals for USDC
-        let value_before_penalty = (dob_amount * nav) / 10_000_000;
-        let usdc_out = (value_before_penalty * (BPS - penalty_bps) as i128) / BPS as i128;
+        let usdc_out = (dob_amount * settlement_price) / 10_000_000;
 
-        RedemptionQuote {
-            usdc_out,
-            penalty_bps,
+        let usdc_client = token::Client::new(&env, &usdc_token);
+        if usdc_client.balance(&env.current_contract_address()) < usdc_out {
+            return Err(Error::InsufficientLiquidity);
+        }
+
+        let burn_args: Vec<soroban_sdk::Val> = (env.current_contract_address(), holder.clone(), dob_amount).into_val(&env);
+        let _: () = env.invoke_contract(
+            &dob_token,
+            &Symbol::new(&env, "minter_burn"),
+            burn_args,
+        );
+
+        usdc_client.transfer(&env.current_contract_address(), &holder, &usdc_out);
+
+        env.events().publish(
+            (Symbol::new(&env, "redeemed_at_maturity"),),
+            SellEvent {
+                seller: holder,
+                dob_in: dob_amount,
+                usdc_out,
+                penalty_bps: 0,
+            },
+        );
+
+        Ok(usdc_out)
+    }
+
+    /// Send USDC left unredeemed to the operator once the grace period after
+    /// maturity has passed (only operator)
+    pub fn sweep_unclaimed(env: Env) -> Result<i128, Error> {
+        let operator: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Operator)
+            .expect("Operator not set");
+
+        operator.require_auth();
+
+        let dob_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DobToken)
+            .expect("DOB token not set");
+        let maturity = Self::maturity(&env, &dob_token).ok_or(Error::NotMatured)?;
+
+        if env.ledger().timestamp() < maturity + Self::maturity_grace(env.clone()) {
+            return Err(Error::GracePeriodActive);
+        }
+
+        let usdc_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::UsdcToken)
+            .expect("USDC token not set");
+        let usdc_client = token::Client::new(&env, &usdc_token);
+        let unclaimed = usdc_client.balance(&env.current_contract_address());
+        if unclaimed > 0 {
+            usdc_client.transfer(&env.current_contract_address(), &operator, &unclaimed);
         }
+
+        env.events()
+            .publish((Symbol::new(&env, "unclaimed_swept"), operator), unclaimed);
+
+        Ok(unclaimed)
     }
 
-    /// Get current NAV from oracle
-    pub fn get_nav(env: Env) -> i128 {
-        let oracle: Address = env
+    /// Set the redemption grace period after maturity (only operator)
+    /// The grace is fixed once the token matures and never shorter than
+    /// MIN_MATURITY_GRACE, so holders' redemption window cannot be cut
+    pub fn set_maturity_grace(env: Env, grace_secs: u64) -> Result<(), Error> {
+        let operator: Address = env
             .storage()
             .instance()
-            .get(&DataKey::Oracle)
-            .expect("Oracle not set");
+            .get(&DataKey::Operator)
+            .expect("Operator not set");
+
+        operator.require_auth();
+
+        if grace_secs < MIN_MATURITY_GRACE {
+            return Err(Error::InvalidGrace);
+        }
+
+        let dob_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DobToken)
+            .expect("DOB token not set");
+        if let Some(maturity) = Self::maturity(&env, &dob_token) {
+            if env.ledger().timestamp() >= maturity {
+                return Err(Error::Matured);
+            }
+        }
+
+        env.storage().instance().set(&DataKey::MaturityGrace, &grace_secs);
+
+        env.events()
+            .publish((Symbol::new(&env, "maturity_grace_set"),), grace_secs);
 
-        env.invoke_contract(&oracle, &Symbol::new(&env, "nav"), soroban_sdk::vec![&env])
+        Ok(())
     }
 
-    /// Get current default risk from oracle
-    pub fn get_default_risk(env: Env) -> u32 {
+    /// Get the redemption grace period after maturity
+    pub fn maturity_grace(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaturityGrace)
+            .unwrap_or(DEFAULT_MATURITY_GRACE)
+    }
+
+    /// Set the amortization schedule (only operator)
+    /// Periods must be due in increasing order and retire between 0 and
+    /// MAX_AMORTIZATION_BPS of each holding; already opened periods are kept
+    pub fn set_amortization_schedule(env: Env, periods: Vec<AmortizationPeriod>) -> Result<(), Error> {
+        let operator: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Operator)
+            .expect("Operator not set");
+
+        operator.require_auth();
+
+        let opened = Self::next_amortization(env.clone());
+        let current = Self::amortization_schedule(env.clone());
+        if periods.len() < opened {
+            return Err(Error::InvalidSchedule);
+        }
+
+        let mut last_due = 0u64;
+        for (index, period) in periods.iter().enumerate() {
+            if period.due_at <= last_due
+                || period.factor_bps == 0
+                || period.factor_bps > MAX_AMORTIZATION_BPS
+            {
+                return Err(Error::InvalidSchedule);
+            }
+            if (index as u32) < opened && current.get_unchecked(index as u32) != period {
+                return Err(Error::InvalidSchedule);
+            }
+            last_due = period.due_at;
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AmortizationSchedule, &periods);
+
+        env.events()
+            .publish((Symbol::new(&env, "amortization_schedule_set"),), periods.len());
+
+        Ok(())
+    }
+
+    /// Get the amortization schedule
+    pub fn amortization_schedule(env: Env) -> Vec<AmortizationPeriod> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AmortizationSchedule)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the index of the next amortization period to open
+    pub fn next_amortization(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::NextAmortization)
+            .unwrap_or(0)
+    }
+
+    /// Open the next due amortization period (anyone, e.g. a keeper)
+    /// Snapshots DOB holdings and fixes the payout price from the oracle, the
+    /// checked fair price or the recovery price while impaired, so every
+    /// holder is paid the same; holders then claim or are paid in batches
+    pub fn open_amortization(env: Env) -> Result<u32, Error> {
+        Self::extend_instance(&env);
+
+        let index = Self::next_amortization(env.clone());
+        let period = Self::amortization_schedule(env.clone())
+            .get(index)
+            .ok_or(Error::AmortizationNotDue)?;
+
+        if env.ledger().timestamp() < period.due_at {
+            return Err(Error::AmortizationNotDue);
+        }
+
+        let dob_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DobToken)
+            .expect("DOB token not set");
         let oracle: Address = env
             .storage()
             .instance()
             .get(&DataKey::Oracle)
             .expect("Oracle not set");
+        let price = match Self::recovery_price(&env, &oracle, &dob_token)? {
+            Some(recovery_price) => recovery_price,
+            None => Self::checked_fair_price(&env, &oracle, &dob_token)?,
+        };
+        let key = DataKey::AmortizationPrice(index);
+        env.storage().persistent().set(&key, &price);
+        Self::extend_persistent(&env, &key);
 
-        env.invoke_contract(&oracle, &Symbol::new(&env, "default_risk"), soroban_sdk::vec![&env])
-    }
+        let snapshot_id: u32 = env.invoke_contract(
+            &dob_token,
+            &Symbol::new(&env, "minter_snapshot"),
+            (env.current_contract_address(),).into_val(&env),
+        );
+        let key = DataKey::AmortizationSnapshot(index);
+        env.storage().persistent().set(&key, &snapshot_id);
+        Self::extend_persistent(&env, &key);
 
-    /// Get contract addresses
-    pub fn get_addresses(env: Env) -> (Address, Address, Address, Address) {
-        let dob_token = env.storage().instance().get(&DataKey::DobToken).unwrap();
-        let usdc_token = env.storage().instance().get(&DataKey::UsdcToken).unwrap();
-        let oracle = env.storage().instance().get(&DataKey::Oracle).unwrap();
-        let operator = env.storage().instance().get(&DataKey::Operator).unwrap();
+        env.storage()
+            .instance()
+            .set(&DataKey::NextAmortization, &(index + 1));
 
-        (dob_token, usdc_token, oracle, operator)
+        env.events().publish(
+            (Symbol::new(&env, "amortization_opened"), index),
+            period.factor_bps,
+        );
+
+        Ok(index)
     }
 
-    /// Get trading statistics
-    pub fn get_stats(env: Env) -> (i128, i128) {
-        let total_bought = env
+    /// Retire the period's share of each holder's tokens, as held when the
+    /// period opened, and pay them USDC (only operator, batching through holders)
+    /// Holders already processed for the period are skipped; the oracle's
+    /// cashflows are scaled down by the supply each batch retires
+    /// Reserve holders (this market, the token's redemption escrow and other
+    /// minters such as the AMM pool) are rejected
+    pub fn amortize_holders(env: Env, period_index: u32, holders: Vec<Address>) -> Result<i128, Error> {
+        let operator: Address = env
             .storage()
             .instance()
-            .get(&DataKey::TotalBought)
-            .unwrap_or(0);
-        let total_sold = env.storage().instance().get(&DataKey::TotalSold).unwrap_or(0);
+            .get(&DataKey::Operator)
+            .expect("Operator not set");
 
-        (total_bought, total_sold)
-    }
+        operator.require_auth();
 
-    /// Fund the contract with USDC for redemptions
-    pub fn fund(env: Env, funder: Address, amount: i128) -> Result<(), Error> {
-        funder.require_auth();
+        Self::extend_instance(&env);
 
-        if amount <= 0 {
-            return Err(Error::InvalidAmount);
+        let mut total_dob_retired = 0i128;
+        let mut total_usdc_out = 0i128;
+        for holder in holders.iter() {
+            let (dob_retired, usdc_out) = Self::amortize_holder(&env, period_index, &holder)?;
+            total_dob_retired += dob_retired;
+            total_usdc_out += usdc_out;
         }
 
-        let usdc_token: Address = env
+        Self::apply_amortization(&env, total_dob_retired);
+
+        Ok(total_usdc_out)
+    }
+
+    /// Claim the holder's own share of an opened amortization period
+    /// Returns the USDC paid, zero if already amortized for the period
+    pub fn claim_amortization(env: Env, holder: Address, period_index: u32) -> Result<i128, Error> {
+        holder.require_auth();
+
+        Self::extend_instance(&env);
+
+        let (dob_retired, usdc_out) = Self::amortize_holder(&env, period_index, &holder)?;
+        Self::apply_amortization(&env, dob_retired);
+
+        Ok(usdc_out)
+    }
+
+    /// Whether a holder was already amortized for a period
+    pub fn is_amortized(env: Env, period_index: u32, holder: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Amortized(period_index, holder))
+    }
+
+    /// Get quote for selling DOB tokens
+    /// Returns expected USDC output and penalty in basis points
+    pub fn quote_redemption(env: Env, dob_amount: i128) -> Result<RedemptionQuote, Error> {
+        let dob_token: Address = env
             .storage()
             .instance()
-            .get(&DataKey::UsdcToken)
-            .expect("USDC token not set");
+            .get(&DataKey::DobToken)
+            .expect("DOB token not set");
+        let oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Oracle)
+            .expect("Oracle not set");
 
-        let usdc_client = token::Client::new(&env, &usdc_token);
-        usdc_client.transfer(&funder, &env.current_contract_address(), &amount);
+        let (nav, penalty_bps) = Self::redemption_pricing(&env, &oracle, &dob_token)?;
+
+        // USDC out = DOB × NAV × (1 - penalty)
+        // DOB is 7 decimals, NAV is 7 decimals
+        // Result should be 7 decimals for USDC
+        let value_before_penalty = (dob_amount * nav) / 10_000_000;
+        let usdc_out = (value_before_penalty * (BPS - penalty_bps) as i128) / BPS as i128;
+
+        Ok(RedemptionQuote {
+            usdc_out,
+            penalty_bps,
+        })
+    }
+
+    /// Set the oracle TWAP window used to price redemptions and floor mints (only operator)
+    /// A window of 0 redeems and mints at the latest spot price
+    pub fn set_twap_window(env: Env, window_secs: u64) -> Result<(), Error> {
+        let operator: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Operator)
+            .expect("Operator not set");
+
+        operator.require_auth();
+
+        env.storage().instance().set(&DataKey::TwapWindow, &window_secs);
 
         env.events()
-            .publish((Symbol::new(&env, "funded"), funder), amount);
+            .publish((Symbol::new(&env, "twap_window_set"),), window_secs);
 
         Ok(())
     }
 
-    /// Get contract USDC balance
-    pub fn get_balance(env: Env) -> i128 {
-        let usdc_token: Address = env
+    /// Get the oracle TWAP window used to price redemptions and floor mints
+    pub fn twap_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TwapWindow)
+            .unwrap_or(DEFAULT_TWAP_WINDOW)
+    }
+
+    /// Set the maximum oracle price age accepted for trades (only operator)
+    pub fn set_heartbeat(env: Env, max_age_secs: u64) -> Result<(), Error> {
+        let operator: Address = env
             .storage()
             .instance()
-            .get(&DataKey::UsdcToken)
-            .expect("USDC token not set");
+            .get(&DataKey::Operator)
+            .expect("Operator not set");
 
-        let usdc_client = token::Client::new(&env, &usdc_token);
-        usdc_client.balance(&env.current_contract_address())
-    }
-}
+        operator.require_auth();
 
-#[cfg(test)]
-mod test {
-    use super::*;
+        env.storage().instance().set(&DataKey::Heartbeat, &max_age_secs);
 
-    #[test]
-    fn test_quote_calculation() {
+        env.events()
+            .publish((Symbol::new(&env, "heartbeat_set"),), max_age_secs);
+
+        Ok(())
+    }
+
+    /// Get the maximum oracle price age accepted for trades
+    pub fn heartbeat(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Heartbeat)
+            .unwrap_or(DEFAULT_HEARTBEAT)
+    }
+
+    /// Propose a new operator, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (only current operator)
+    pub fn propose_operator(env: Env, new_operator: Address, expires_at: Option<u64>) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).expect("Operator not set");
+        operator.require_auth();
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let proposal = OperatorProposal {
+            proposed: new_operator.clone(),
+            expires_at,
+        };
+        env.storage().instance().set(&DataKey::PendingOperator, &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_proposed"), operator),
+            proposal,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending operator proposal (only the proposed address)
+    pub fn accept_operator(env: Env) -> Result<(), Error> {
+        let proposal = Self::pending_operator(env.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
+
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let previous: Address = env.storage().instance().get(&DataKey::Operator).expect("Operator not set");
+        env.storage().instance().set(&DataKey::Operator, &proposal.proposed);
+        env.storage().instance().remove(&DataKey::PendingOperator);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_changed"), previous),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the pending operator proposal (only current operator)
+    pub fn cancel_proposal(env: Env) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).expect("Operator not set");
+        operator.require_auth();
+
+        let proposal = Self::pending_operator(env.clone()).ok_or(Error::NoProposal)?;
+        env.storage().instance().remove(&DataKey::PendingOperator);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_proposal_cancelled"), operator),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Get the pending operator proposal, if any
+    pub fn pending_operator(env: Env) -> Option<OperatorProposal> {
+        env.storage().instance().get(&DataKey::PendingOperator)
+    }
+
+    /// Get current NAV from oracle
+    pub fn get_nav(env: Env) -> i128 {
+        let dob_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DobToken)
+            .expect("DOB token not set");
+        let oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Oracle)
+            .expect("Oracle not set");
+
+        env.invoke_contract(&oracle, &Symbol::new(&env, "fair_price"), (dob_token,).into_val(&env))
+    }
+
+    /// Get current default risk from oracle
+    pub fn get_default_risk(env: Env) -> u32 {
+        let dob_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DobToken)
+            .expect("DOB token not set");
+        let oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Oracle)
+            .expect("Oracle not set");
+
+        env.invoke_contract(&oracle, &Symbol::new(&env, "default_risk"), (dob_token,).into_val(&env))
+    }
+
+    /// Get contract addresses
+    pub fn get_addresses(env: Env) -> (Address, Address, Address, Address) {
+        let dob_token = env.storage().instance().get(&DataKey::DobToken).unwrap();
+        let usdc_token = env.storage().instance().get(&DataKey::UsdcToken).unwrap();
+        let oracle = env.storage().instance().get(&DataKey::Oracle).unwrap();
+        let operator = env.storage().instance().get(&DataKey::Operator).unwrap();
+
+        (dob_token, usdc_token, oracle, operator)
+    }
+
+    /// Get trading statistics
+    pub fn get_stats(env: Env) -> (i128, i128) {
+        let total_bought = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalBought)
+            .unwrap_or(0);
+        let total_sold = env.storage().instance().get(&DataKey::TotalSold).unwrap_or(0);
+
+        (total_bought, total_sold)
+    }
+
+    /// Fund the contract with USDC for redemptions
+    pub fn fund(env: Env, funder: Address, amount: i128) -> Result<(), Error> {
+        funder.require_auth();
+
+        Self::extend_instance(&env);
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let usdc_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::UsdcToken)
+            .expect("USDC token not set");
+
+        let usdc_client = token::Client::new(&env, &usdc_token);
+        usdc_client.transfer(&funder, &env.current_contract_address(), &amount);
+
+        env.events()
+            .publish((Symbol::new(&env, "funded"), funder), amount);
+
+        Ok(())
+    }
+
+    /// Get contract USDC balance
+    pub fn get_balance(env: Env) -> i128 {
+        let usdc_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::UsdcToken)
+            .expect("USDC token not set");
+
+        let usdc_client = token::Client::new(&env, &usdc_token);
+        usdc_client.balance(&env.current_contract_address())
+    }
+
+    /// Get admin address
+    pub fn admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
+
+    /// Propose a new admin, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (only current admin)
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: Option<u64>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let proposal = AdminProposal {
+            proposed: new_admin.clone(),
+            expires_at,
+        };
+        env.storage().instance().set(&DataKey::PendingAdmin, &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposed"), admin),
+            proposal,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending admin proposal (only the proposed address)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
+
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let previous = Self::admin(env.clone());
+        env.storage().instance().set(&DataKey::Admin, &proposal.proposed);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_changed"), previous),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the pending admin proposal (only current admin)
+    pub fn cancel_admin_proposal(env: Env) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposal_cancelled"), admin),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Get the pending admin proposal, if any
+    pub fn pending_admin(env: Env) -> Option<AdminProposal> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    /// Set the storage TTL thresholds (only admin)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if config.instance_threshold > config.instance_extend_to
+            || config.persistent_threshold > config.persistent_extend_to
+            || config.instance_extend_to > env.storage().max_ttl()
+            || config.persistent_extend_to > env.storage().max_ttl()
+        {
+            return Err(Error::InvalidTtl);
+        }
+
+        env.storage().instance().set(&DataKey::TtlConfig, &config);
+        Self::extend_instance(&env);
+
+        env.events().publish(
+            (Symbol::new(&env, "ttl_config_set"), admin),
+            config,
+        );
+
+        Ok(())
+    }
+
+    /// Get the storage TTL thresholds
+    pub fn ttl_config(env: Env) -> TtlConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TtlConfig)
+            .unwrap_or(TtlConfig {
+                instance_threshold: INSTANCE_LIFETIME_THRESHOLD,
+                instance_extend_to: INSTANCE_BUMP_AMOUNT,
+                persistent_threshold: PERSISTENT_LIFETIME_THRESHOLD,
+                persistent_extend_to: PERSISTENT_BUMP_AMOUNT,
+            })
+    }
+
+    /// Replace the contract code (only admin)
+    /// Call `migrate` afterwards when the new code raises the schema version
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "upgraded"), admin),
+            new_wasm_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Apply storage layout changes up to the current schema version (only admin)
+    /// Contracts initialized before versioning report version 0
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let from_version = Self::schema_version(env.clone());
+        if from_version >= SCHEMA_VERSION {
+            return Err(Error::AlreadyMigrated);
+        }
+
+        // Version 1 introduced no layout change; later steps go here keyed on `from_version`
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+
+        env.events().publish(
+            (Symbol::new(&env, "migrated"), admin),
+            (from_version, SCHEMA_VERSION),
+        );
+
+        Ok(SCHEMA_VERSION)
+    }
+
+    /// Get the storage layout version
+    pub fn schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(0)
+    }
+}
+
+impl DobPrimaryMarket {
+    fn extend_instance(env: &Env) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .instance()
+            .extend_ttl(config.instance_threshold, config.instance_extend_to);
+    }
+
+    /// Extend an existing persistent entry
+    fn extend_persistent(env: &Env, key: &DataKey) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .persistent()
+            .extend_ttl(key, config.persistent_threshold, config.persistent_extend_to);
+    }
+
+    /// Read the oracle fair price, failing if it is older than the heartbeat
+    /// or the oracle is frozen
+    fn checked_fair_price(env: &Env, oracle: &Address, dob_token: &Address) -> Result<i128, Error> {
+        let heartbeat = Self::heartbeat(env.clone());
+        Ok(dob_oracle_client::fair_price_checked(env, oracle, dob_token, heartbeat)?)
+    }
+
+    /// Retire the period's share of a holder's tokens, as held when the period
+    /// opened and capped by what they still hold, and pay it at the period's
+    /// price; returns (dob_retired, usdc_out), zero once already amortized
+    fn amortize_holder(env: &Env, period_index: u32, holder: &Address) -> Result<(i128, i128), Error> {
+        if period_index >= Self::next_amortization(env.clone()) {
+            return Err(Error::PeriodNotOpen);
+        }
+
+        let period = Self::amortization_schedule(env.clone()).get_unchecked(period_index);
+        let snapshot_id: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AmortizationSnapshot(period_index))
+            .ok_or(Error::PeriodNotOpen)?;
+        let price: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AmortizationPrice(period_index))
+            .ok_or(Error::PeriodNotOpen)?;
+
+        let dob_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DobToken)
+            .expect("DOB token not set");
+        let usdc_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::UsdcToken)
+            .expect("USDC token not set");
+
+        if Self::is_reserve_holder(env, &dob_token, holder) {
+            return Err(Error::ReserveHolder);
+        }
+
+        let key = DataKey::Amortized(period_index, holder.clone());
+        if env.storage().persistent().has(&key) {
+            return Ok((0, 0));
+        }
+        env.storage().persistent().set(&key, &true);
+        Self::extend_persistent(env, &key);
+
+        let held_at_open: i128 = env.invoke_contract(
+            &dob_token,
+            &Symbol::new(env, "balance_of_at"),
+            (holder.clone(), snapshot_id).into_val(env),
+        );
+        let dob_retired = ((held_at_open * period.factor_bps as i128) / BPS as i128)
+            .min(token::Client::new(env, &dob_token).balance(holder));
+        if dob_retired <= 0 {
+            return Ok((0, 0));
+        }
+
+        let usdc_out = (dob_retired * price) / 10_000_000;
+        let usdc_client = token::Client::new(env, &usdc_token);
+        if usdc_client.balance(&env.current_contract_address()) < usdc_out {
+            return Err(Error::InsufficientLiquidity);
+        }
+
+        let burn_args: Vec<soroban_sdk::Val> = (env.current_contract_address(), holder.clone(), dob_retired).into_val(env);
+        let _: () = env.invoke_contract(
+            &dob_token,
+            &Symbol::new(env, "minter_burn"),
+            burn_args,
+        );
+        usdc_client.transfer(&env.current_contract_address(), holder, &usdc_out);
+
+        env.events().publish(
+            (Symbol::new(env, "amortized"), period_index),
+            AmortizedEvent {
+                holder: holder.clone(),
+                dob_retired,
+                usdc_out,
+            },
+        );
+
+        Ok((dob_retired, usdc_out))
+    }
+
+    /// Scale the oracle's cashflows down by the supply just retired
+    fn apply_amortization(env: &Env, dob_retired: i128) {
+        if dob_retired <= 0 {
+            return;
+        }
+
+        let dob_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DobToken)
+            .expect("DOB token not set");
+        let oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Oracle)
+            .expect("Oracle not set");
+        let _: () = env.invoke_contract(
+            &oracle,
+            &Symbol::new(env, "apply_amortization"),
+            (dob_token, dob_retired).into_val(env),
+        );
+    }
+
+    /// Contracts holding DOB as reserves rather than for themselves: this
+    /// market, the token's redemption escrow and any other minter such as the AMM pool
+    fn is_reserve_holder(env: &Env, dob_token: &Address, holder: &Address) -> bool {
+        if *holder == env.current_contract_address() || holder == dob_token {
+            return true;
+        }
+
+        let minter_config: Option<soroban_sdk::Val> = env.invoke_contract(
+            dob_token,
+            &Symbol::new(env, "minter_config"),
+            (holder.clone(),).into_val(env),
+        );
+        minter_config.is_some()
+    }
+
+    /// Recovery price the oracle settles an impaired series at, failing if
+    /// the oracle is frozen
+    fn recovery_price(env: &Env, oracle: &Address, dob_token: &Address) -> Result<Option<i128>, Error> {
+        Ok(dob_oracle_client::recovery_redemption_price(env, oracle, dob_token)?)
+    }
+
+    /// Price matured tokens redeem at: the recovery price while the series is
+    /// Defaulted or Recovered, otherwise the final settlement price
+    /// Fails if the oracle is frozen or no settlement price is set
+    fn settlement_price(env: &Env, oracle: &Address, dob_token: &Address) -> Result<i128, Error> {
+        if let Some(recovery_price) = Self::recovery_price(env, oracle, dob_token)? {
+            return Ok(recovery_price);
+        }
+
+        let settlement_price: Option<i128> = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "settlement_price"),
+            (dob_token.clone(),).into_val(env),
+        );
+        settlement_price.ok_or(Error::NotSettled)
+    }
+
+    /// Maturity timestamp of the DOB token, if set
+    fn maturity(env: &Env, dob_token: &Address) -> Option<u64> {
+        env.invoke_contract(dob_token, &Symbol::new(env, "maturity"), soroban_sdk::vec![env])
+    }
+
+    /// Price to mint at: the higher of the checked spot price and the TWAP
+    fn mint_price(env: &Env, oracle: &Address, dob_token: &Address) -> Result<i128, Error> {
+        let spot = Self::checked_fair_price(env, oracle, dob_token)?;
+
+        let twap_window = Self::twap_window(env.clone());
+        let twap: i128 = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "twap"),
+            (dob_token.clone(), twap_window).into_val(env),
+        );
+
+        Ok(spot.max(twap))
+    }
+
+    /// Fail with `Error::IssuanceClosed` once the oracle reports the series
+    /// Defaulted, Recovered or Matured
+    fn ensure_issuance_open(env: &Env, oracle: &Address, dob_token: &Address) -> Result<(), Error> {
+        let open: bool = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "issuance_open"),
+            (dob_token.clone(),).into_val(env),
+        );
+        if !open {
+            return Err(Error::IssuanceClosed);
+        }
+        Ok(())
+    }
+
+    /// Price and penalty to redeem at: the oracle recovery price without a
+    /// penalty once the series is impaired, otherwise the TWAP less the
+    /// penalty from the oracle's governance-controlled curve
+    fn redemption_pricing(env: &Env, oracle: &Address, dob_token: &Address) -> Result<(i128, u32), Error> {
+        // An impaired series settles at its recovery price, which the heartbeat
+        // does not apply to since the fair price is no longer pushed
+        if let Some(recovery_price) = Self::recovery_price(env, oracle, dob_token)? {
+            return Ok((recovery_price, 0));
+        }
+
+        // Refuse to price against a feed older than the heartbeat
+        Self::checked_fair_price(env, oracle, dob_token)?;
+
+        // Time-weighted price so a single bad push cannot reprice redemptions instantly
+        let twap_window = Self::twap_window(env.clone());
+        let price: i128 = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "twap"),
+            (dob_token.clone(), twap_window).into_val(env),
+        );
+        let risk: u32 = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "default_risk"),
+            (dob_token.clone(),).into_val(env),
+        );
+        let penalty_bps: u32 = env.invoke_contract(
+            oracle,
+            &Symbol::new(env, "calculate_penalty"),
+            (dob_token.clone(), risk).into_val(env),
+        );
+
+        Ok((price, penalty_bps))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        contract, contracterror, contractimpl, symbol_short,
+        testutils::{Address as _, Ledger},
+        token::StellarAssetClient,
+        vec,
+    };
+
+    /// Oracle stand-in whose feed can be marked stale or frozen
+    #[contract]
+    pub struct MockOracle;
+
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+    #[repr(u32)]
+    pub enum OracleError {
+        Stale = 6,
+        Paused = 21,
+    }
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_stale(env: Env, stale: bool) {
+            env.storage().instance().set(&symbol_short!("stale"), &stale);
+        }
+
+        pub fn set_paused(env: Env, paused: bool) {
+            env.storage().instance().set(&symbol_short!("paused"), &paused);
+        }
+
+        pub fn fair_price_checked(env: Env, _asset: Address, _max_age: u64) -> Result<i128, OracleError> {
+            if Self::flag(&env, symbol_short!("paused")) {
+                return Err(OracleError::Paused);
+            }
+            if Self::flag(&env, symbol_short!("stale")) {
+                return Err(OracleError::Stale);
+            }
+            Ok(env
+                .storage()
+                .instance()
+                .get(&symbol_short!("price"))
+                .unwrap_or(10_000_000))
+        }
+
+        pub fn twap(_env: Env, _asset: Address, _window: u64) -> i128 {
+            10_000_000
+        }
+
+        pub fn default_risk(_env: Env, _asset: Address) -> u32 {
+            1000
+        }
+
+        pub fn calculate_penalty(_env: Env, _asset: Address, _risk: u32) -> u32 {
+            400
+        }
+
+        pub fn issuance_open(_env: Env, _asset: Address) -> bool {
+            true
+        }
+
+        pub fn recovery_redemption_price(env: Env, _asset: Address) -> Result<Option<i128>, OracleError> {
+            if Self::flag(&env, symbol_short!("paused")) {
+                return Err(OracleError::Paused);
+            }
+            Ok(None)
+        }
+
+        pub fn settlement_price(_env: Env, _asset: Address) -> Option<i128> {
+            Some(10_000_000)
+        }
+
+        pub fn set_price(env: Env, price: i128) {
+            env.storage().instance().set(&symbol_short!("price"), &price);
+        }
+
+        pub fn apply_amortization(env: Env, _asset: Address, retired: i128) {
+            let total: i128 = Self::amortized(env.clone());
+            env.storage().instance().set(&symbol_short!("retired"), &(total + retired));
+        }
+
+        pub fn amortized(env: Env) -> i128 {
+            env.storage().instance().get(&symbol_short!("retired")).unwrap_or(0)
+        }
+    }
+
+    impl MockOracle {
+        fn flag(env: &Env, name: Symbol) -> bool {
+            env.storage().instance().get(&name).unwrap_or(false)
+        }
+    }
+
+    /// DOB token stand-in with balances, minters, one holder snapshot and
+    /// the maturity timestamp
+    #[contract]
+    pub struct MockDobToken;
+
+    #[contracttype]
+    pub enum MockKey {
+        Balance(Address),
+        Holders,
+        Snapshot(Address),
+        Minter(Address),
+    }
+
+    #[contractimpl]
+    impl MockDobToken {
+        pub fn set_maturity(env: Env, maturity: u64) {
+            env.storage().instance().set(&symbol_short!("maturity"), &maturity);
+        }
+
+        pub fn maturity(env: Env) -> Option<u64> {
+            env.storage().instance().get(&symbol_short!("maturity"))
+        }
+
+        pub fn set_minter(env: Env, minter: Address) {
+            env.storage().instance().set(&MockKey::Minter(minter), &true);
+        }
+
+        pub fn minter_config(env: Env, minter: Address) -> Option<bool> {
+            env.storage().instance().get(&MockKey::Minter(minter))
+        }
+
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let mut holders: Vec<Address> = env
+                .storage()
+                .instance()
+                .get(&MockKey::Holders)
+                .unwrap_or(Vec::new(&env));
+            if !holders.contains(&to) {
+                holders.push_back(to.clone());
+                env.storage().instance().set(&MockKey::Holders, &holders);
+            }
+            Self::write(&env, &to, Self::balance(env.clone(), to.clone()) + amount);
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            Self::write(&env, &from, Self::balance(env.clone(), from.clone()) - amount);
+            Self::mint(env, to, amount);
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().instance().get(&MockKey::Balance(id)).unwrap_or(0)
+        }
+
+        pub fn minter_snapshot(env: Env, _minter: Address) -> u32 {
+            let holders: Vec<Address> = env
+                .storage()
+                .instance()
+                .get(&MockKey::Holders)
+                .unwrap_or(Vec::new(&env));
+            for holder in holders.iter() {
+                let balance = Self::balance(env.clone(), holder.clone());
+                env.storage().instance().set(&MockKey::Snapshot(holder), &balance);
+            }
+            1
+        }
+
+        pub fn balance_of_at(env: Env, account: Address, _snapshot_id: u32) -> i128 {
+            env.storage().instance().get(&MockKey::Snapshot(account)).unwrap_or(0)
+        }
+
+        pub fn minter_burn(env: Env, _minter: Address, from: Address, amount: i128) {
+            Self::write(&env, &from, Self::balance(env.clone(), from.clone()) - amount);
+        }
+    }
+
+    impl MockDobToken {
+        fn write(env: &Env, id: &Address, balance: i128) {
+            env.storage().instance().set(&MockKey::Balance(id.clone()), &balance);
+        }
+    }
+
+    fn setup(env: &Env) -> (DobPrimaryMarketClient<'_>, MockOracleClient<'_>, MockDobTokenClient<'_>) {
+        let oracle = env.register_contract(None, MockOracle);
+        let dob_token = env.register_contract(None, MockDobToken);
+        let usdc_token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        let contract_id = env.register_contract(None, DobPrimaryMarket);
+        let client = DobPrimaryMarketClient::new(env, &contract_id);
+
+        env.mock_all_auths();
+        client.initialize(
+            &Address::generate(env),
+            &dob_token,
+            &usdc_token,
+            &oracle,
+            &Address::generate(env),
+        );
+
+        (
+            client,
+            MockOracleClient::new(env, &oracle),
+            MockDobTokenClient::new(env, &dob_token),
+        )
+    }
+
+    #[test]
+    fn test_stale_oracle_blocks_pricing() {
+        let env = Env::default();
+        let (client, oracle, _) = setup(&env);
+        let buyer = Address::generate(&env);
+
+        // 1000 DOB at 1.00 less the 4% penalty
+        assert_eq!(client.quote_redemption(&1000_0000000).usdc_out, 960_0000000);
+
+        oracle.set_stale(&true);
+        assert_eq!(client.try_quote_redemption(&1000_0000000), Err(Ok(Error::StalePrice)));
+        assert_eq!(client.try_buy(&buyer, &100_0000000), Err(Ok(Error::StalePrice)));
+    }
+
+    #[test]
+    fn test_paused_oracle_blocks_pricing() {
+        let env = Env::default();
+        let (client, oracle, dob_token) = setup(&env);
+        let holder = Address::generate(&env);
+
+        oracle.set_paused(&true);
+        assert_eq!(client.try_quote_redemption(&1000_0000000), Err(Ok(Error::OraclePaused)));
+        assert_eq!(client.try_buy(&holder, &100_0000000), Err(Ok(Error::OraclePaused)));
+
+        // A frozen oracle also halts settlement at maturity
+        dob_token.set_maturity(&env.ledger().timestamp());
+        assert_eq!(
+            client.try_redeem_at_maturity(&holder, &1000_0000000),
+            Err(Ok(Error::OraclePaused))
+        );
+    }
+
+    #[test]
+    fn test_quote_calculation() {
         // This is a simplified test - full integration tests would need
         // deployed oracle and token contracts
         // Penalty = 300 + 1000/10 = 400 bps = 4%
@@ -344,4 +1519,253 @@ mod test {
         // Value = 1000 * 1.00 = 1000 USDC
         // After 4% penalty = 1000 * 0.96 = 960 USDC
     }
+
+    #[test]
+    fn test_amortization_schedule() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+        let period = |due_at, factor_bps| AmortizationPeriod { due_at, factor_bps };
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+        // Due dates must increase and factors stay within the per-period bound
+        for periods in [
+            vec![&env, period(2_000, 1000), period(2_000, 1000)],
+            vec![&env, period(2_000, 0)],
+            vec![&env, period(2_000, MAX_AMORTIZATION_BPS + 1)],
+        ] {
+            assert_eq!(
+                client.try_set_amortization_schedule(&periods),
+                Err(Ok(Error::InvalidSchedule))
+            );
+        }
+
+        let schedule = vec![&env, period(2_000, 1000), period(3_000, MAX_AMORTIZATION_BPS)];
+        client.set_amortization_schedule(&schedule);
+        assert_eq!(client.amortization_schedule(), schedule);
+        assert_eq!(client.try_open_amortization(), Err(Ok(Error::AmortizationNotDue)));
+        assert_eq!(
+            client.try_claim_amortization(&Address::generate(&env), &0),
+            Err(Ok(Error::PeriodNotOpen))
+        );
+
+        env.ledger().with_mut(|li| li.timestamp = 2_000);
+        assert_eq!(client.open_amortization(), 0);
+        assert_eq!(client.try_open_amortization(), Err(Ok(Error::AmortizationNotDue)));
+
+        // An opened period cannot be rewritten, later ones can
+        assert_eq!(
+            client.try_set_amortization_schedule(&vec![&env, period(2_000, 2000)]),
+            Err(Ok(Error::InvalidSchedule))
+        );
+        client.set_amortization_schedule(&vec![&env, period(2_000, 1000), period(4_000, 500)]);
+    }
+
+    #[test]
+    fn test_amortization_snapshot_and_payout() {
+        let env = Env::default();
+        let (client, oracle, dob_token) = setup(&env);
+        let (_, usdc_token, _, _) = client.get_addresses();
+        let usdc = token::Client::new(&env, &usdc_token);
+        StellarAssetClient::new(&env, &usdc_token).mint(&client.address, &1000_0000000);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        dob_token.mint(&alice, &1000_0000000);
+        dob_token.mint(&bob, &400_0000000);
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.set_amortization_schedule(&vec![
+            &env,
+            AmortizationPeriod {
+                due_at: 1_000,
+                factor_bps: 1000,
+            },
+        ]);
+
+        // The payout price is fixed from the oracle when the period opens
+        oracle.set_price(&12_000_000);
+        client.open_amortization();
+        oracle.set_price(&5_000_000);
+
+        // Shares follow holdings at opening, so tokens moved afterwards carry none
+        dob_token.transfer(&alice, &carol, &500_0000000);
+
+        // 10% of Alice's 1000 DOB at 1.20
+        assert_eq!(client.claim_amortization(&alice, &0), 120_0000000);
+        assert_eq!(env.auths()[0].0, alice);
+        assert_eq!(dob_token.balance(&alice), 400_0000000);
+        assert_eq!(usdc.balance(&alice), 120_0000000);
+        assert_eq!(client.claim_amortization(&alice, &0), 0);
+        assert_eq!(client.claim_amortization(&carol, &0), 0);
+
+        // Batches skip holders who already claimed
+        assert_eq!(client.amortize_holders(&0, &vec![&env, alice.clone(), bob.clone()]), 48_0000000);
+        assert!(client.is_amortized(&0, &bob));
+        assert_eq!(dob_token.balance(&bob), 360_0000000);
+        assert_eq!(usdc.balance(&bob), 48_0000000);
+
+        // The oracle scales cashflows by everything retired
+        assert_eq!(oracle.amortized(), 140_0000000);
+    }
+
+    #[test]
+    fn test_amortization_rejects_reserve_holders() {
+        let env = Env::default();
+        let (client, _, dob_token) = setup(&env);
+        let pool = Address::generate(&env);
+        dob_token.set_minter(&pool);
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        client.set_amortization_schedule(&vec![
+            &env,
+            AmortizationPeriod {
+                due_at: 1_000,
+                factor_bps: 1000,
+            },
+        ]);
+        client.open_amortization();
+
+        // The market, the token's redemption escrow and other minters hold DOB as reserves
+        for holder in [client.address.clone(), dob_token.address.clone(), pool] {
+            assert_eq!(
+                client.try_amortize_holders(&0, &vec![&env, holder.clone()]),
+                Err(Ok(Error::ReserveHolder))
+            );
+            assert_eq!(
+                client.try_claim_amortization(&holder, &0),
+                Err(Ok(Error::ReserveHolder))
+            );
+        }
+    }
+
+    #[test]
+    fn test_maturity_grace_setter() {
+        let env = Env::default();
+        let (client, _, dob_token) = setup(&env);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        dob_token.set_maturity(&10_000);
+
+        assert_eq!(
+            client.try_set_maturity_grace(&(29 * 86_400)),
+            Err(Ok(Error::InvalidGrace))
+        );
+        client.set_maturity_grace(&(30 * 86_400));
+        assert_eq!(client.maturity_grace(), 30 * 86_400);
+
+        // Fixed from maturity on
+        env.ledger().with_mut(|li| li.timestamp = 10_000);
+        assert_eq!(
+            client.try_set_maturity_grace(&(90 * 86_400)),
+            Err(Ok(Error::Matured))
+        );
+        assert_eq!(client.maturity_grace(), 30 * 86_400);
+    }
+
+    #[test]
+    fn test_maturity_redemption_and_sweep() {
+        let env = Env::default();
+        let (client, _, dob_token) = setup(&env);
+        let (_, usdc_token, _, operator) = client.get_addresses();
+        let usdc = token::Client::new(&env, &usdc_token);
+        StellarAssetClient::new(&env, &usdc_token).mint(&client.address, &1000_0000000);
+
+        let holder = Address::generate(&env);
+        dob_token.mint(&holder, &300_0000000);
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        dob_token.set_maturity(&10_000);
+
+        assert_eq!(
+            client.try_redeem_at_maturity(&holder, &100_0000000),
+            Err(Ok(Error::NotMatured))
+        );
+
+        // Redeemed at the 1.00 settlement price
+        env.ledger().with_mut(|li| li.timestamp = 10_000);
+        assert_eq!(client.redeem_at_maturity(&holder, &100_0000000), 100_0000000);
+        assert_eq!(env.auths()[0].0, holder);
+        assert_eq!(dob_token.balance(&holder), 200_0000000);
+        assert_eq!(usdc.balance(&holder), 100_0000000);
+
+        // Unredeemed USDC stays put for the whole grace period
+        let grace = client.maturity_grace();
+        env.ledger().with_mut(|li| li.timestamp = 10_000 + grace - 1);
+        assert_eq!(client.try_sweep_unclaimed(), Err(Ok(Error::GracePeriodActive)));
+        assert_eq!(client.redeem_at_maturity(&holder, &100_0000000), 100_0000000);
+
+        env.ledger().with_mut(|li| li.timestamp = 10_000 + grace);
+        assert_eq!(client.sweep_unclaimed(), 800_0000000);
+        assert_eq!(env.auths()[0].0, operator);
+        assert_eq!(usdc.balance(&operator), 800_0000000);
+        assert_eq!(usdc.balance(&client.address), 0);
+    }
+
+    #[test]
+    fn test_operator_handover() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+        let operator = client.get_addresses().3;
+        let new_operator = Address::generate(&env);
+
+        // Proposals must expire in the future and lapse once that passes
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        assert_eq!(
+            client.try_propose_operator(&new_operator, &Some(1_000)),
+            Err(Ok(Error::ProposalExpired))
+        );
+        client.propose_operator(&new_operator, &Some(2_000));
+        env.ledger().with_mut(|li| li.timestamp = 2_001);
+        assert_eq!(client.try_accept_operator(), Err(Ok(Error::ProposalExpired)));
+
+        client.cancel_proposal();
+        assert_eq!(env.auths()[0].0, operator);
+        assert_eq!(client.pending_operator(), None);
+        assert_eq!(client.try_accept_operator(), Err(Ok(Error::NoProposal)));
+        assert_eq!(client.try_cancel_proposal(), Err(Ok(Error::NoProposal)));
+
+        // The role only moves once the proposed address accepts
+        client.propose_operator(&new_operator, &None);
+        assert_eq!(client.get_addresses().3, operator);
+        client.accept_operator();
+        assert_eq!(env.auths()[0].0, new_operator);
+        assert_eq!(client.get_addresses().3, new_operator);
+        assert_eq!(client.pending_operator(), None);
+
+        client.set_heartbeat(&600);
+        assert_eq!(env.auths()[0].0, new_operator);
+    }
+
+    #[test]
+    fn test_admin_handover() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+        let admin = client.admin();
+        let new_admin = Address::generate(&env);
+
+        // Upgrade-level settings answer to the admin, not the operator
+        client.set_ttl_config(&client.ttl_config());
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(client.try_migrate(), Err(Ok(Error::AlreadyMigrated)));
+
+        // A lapsed proposal cannot be accepted
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        assert_eq!(
+            client.try_propose_admin(&new_admin, &Some(1_000)),
+            Err(Ok(Error::ProposalExpired))
+        );
+        client.propose_admin(&new_admin, &Some(2_000));
+        env.ledger().with_mut(|li| li.timestamp = 2_001);
+        assert_eq!(client.try_accept_admin(), Err(Ok(Error::ProposalExpired)));
+
+        client.cancel_admin_proposal();
+        assert_eq!(client.pending_admin(), None);
+        assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoProposal)));
+
+        client.propose_admin(&new_admin, &None);
+        assert_eq!(client.admin(), admin);
+        client.accept_admin();
+        assert_eq!(env.auths()[0].0, new_admin);
+        assert_eq!(client.admin(), new_admin);
+        assert_eq!(client.pending_admin(), None);
+    }
 }
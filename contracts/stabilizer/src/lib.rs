@@ -1,6 +1,10 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, contracterror, token, Address, Env, IntoVal, Symbol};
+use dob_oracle_client::OracleFailure;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, contracterror, token, Address, BytesN, Env, IntoVal,
+    Symbol,
+};
 
 /// Storage keys for the stabilizer contract
 #[contracttype]
@@ -10,8 +14,14 @@ pub enum DataKey {
     UsdcToken,        // USDC token address
     DobToken,         // DOB token address
     Operator,         // Operator address
+    Admin,            // Admin upgrading the contract, separate from the operator
     TotalFeesEarned,  // Total fees earned from interventions
     AmmPool,          // AMM Pool address (for registration)
+    Heartbeat,        // Max age in seconds of an oracle price we trade against
+    PendingOperator,  // OperatorProposal awaiting acceptance
+    PendingAdmin,     // AdminProposal awaiting acceptance
+    TtlConfig,        // Storage TTL thresholds, defaults when unset
+    SchemaVersion,    // Storage layout version applied by migrate()
 }
 
 /// Liquidity provision event
@@ -24,6 +34,31 @@ pub struct LiquidityProvidedEvent {
     pub fee_bps: u32,
 }
 
+/// Pending operator handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// Pending admin handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// Ledgers before expiry at which the instance is extended, and the TTL it
+/// is extended to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    pub instance_threshold: u32,
+    pub instance_extend_to: u32,
+}
+
 /// Errors that can be returned by the contract
 #[contracterror]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -31,9 +66,29 @@ pub enum Error {
     Unauthorized = 1,
     InsufficientBalance = 2,
     InvalidAmount = 3,
+    StalePrice = 4,
+    OraclePaused = 5,
+    NoProposal = 6,
+    ProposalExpired = 7,
+    AlreadyMigrated = 8,
+    InvalidTtl = 9,
+}
+
+impl From<OracleFailure> for Error {
+    fn from(failure: OracleFailure) -> Self {
+        match failure {
+            OracleFailure::Stale => Error::StalePrice,
+            OracleFailure::Paused => Error::OraclePaused,
+        }
+    }
 }
 
 const BPS: u32 = 10000;
+const DEFAULT_HEARTBEAT: u64 = 86_400; // Reject oracle prices older than 1 day
+const DAY_IN_LEDGERS: u32 = 17280; // ~5s per ledger
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const SCHEMA_VERSION: u32 = 1; // Bump together with a migrate() step
 
 /// LiquidNodeStabilizer
 /// Pre-funded buffer that provides instant liquidity on-demand
@@ -44,8 +99,10 @@ pub struct LiquidNodeStabilizer;
 #[contractimpl]
 impl LiquidNodeStabilizer {
     /// Initialize the stabilizer contract
+    /// The admin upgrades the node; the operator funds it and withdraws its USDC
     pub fn initialize(
         env: Env,
+        admin: Address,
         oracle: Address,
         usdc_token: Address,
         dob_token: Address,
@@ -56,10 +113,14 @@ impl LiquidNodeStabilizer {
             panic!("Already initialized");
         }
 
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::Oracle, &oracle);
         env.storage().instance().set(&DataKey::UsdcToken, &usdc_token);
         env.storage().instance().set(&DataKey::DobToken, &dob_token);
         env.storage().instance().set(&DataKey::Operator, &operator);
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
         env.storage().instance().set(&DataKey::AmmPool, &amm_pool);
         env.storage().instance().set(&DataKey::TotalFeesEarned, &0i128);
     }
@@ -68,6 +129,8 @@ impl LiquidNodeStabilizer {
     pub fn fund_usdc(env: Env, funder: Address, amount: i128) -> Result<(), Error> {
         funder.require_auth();
 
+        Self::extend_instance(&env);
+
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -87,6 +150,8 @@ impl LiquidNodeStabilizer {
     pub fn fund_dob(env: Env, funder: Address, amount: i128) -> Result<(), Error> {
         funder.require_auth();
 
+        Self::extend_instance(&env);
+
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -112,10 +177,15 @@ impl LiquidNodeStabilizer {
 
         let oracle: Address = env.storage().instance().get(&DataKey::Oracle).unwrap();
         let usdc_token: Address = env.storage().instance().get(&DataKey::UsdcToken).unwrap();
+        let dob_token: Address = env.storage().instance().get(&DataKey::DobToken).unwrap();
 
         // Get NAV and risk from oracle
-        let nav: i128 = env.invoke_contract(&oracle, &Symbol::new(&env, "fair_price"), soroban_sdk::vec![&env]);
-        let risk: u32 = env.invoke_contract(&oracle, &Symbol::new(&env, "default_risk"), soroban_sdk::vec![&env]);
+        let nav = Self::checked_fair_price(&env, &oracle, &dob_token)?;
+        let risk: u32 = env.invoke_contract(
+            &oracle,
+            &Symbol::new(&env, "default_risk"),
+            (dob_token.clone(),).into_val(&env),
+        );
 
         // Dynamic fee calculation based on risk
         // Low risk (<15%): 5% fee
@@ -155,16 +225,23 @@ impl LiquidNodeStabilizer {
         let amm_pool: Address = env.storage().instance().get(&DataKey::AmmPool).unwrap();
         amm_pool.require_auth();
 
+        Self::extend_instance(&env);
+
         if dob_amount <= 0 {
             return Err(Error::InvalidAmount);
         }
 
         let oracle: Address = env.storage().instance().get(&DataKey::Oracle).unwrap();
         let usdc_token: Address = env.storage().instance().get(&DataKey::UsdcToken).unwrap();
+        let dob_token: Address = env.storage().instance().get(&DataKey::DobToken).unwrap();
 
         // Get NAV and risk from oracle
-        let nav: i128 = env.invoke_contract(&oracle, &Symbol::new(&env, "fair_price"), soroban_sdk::vec![&env]);
-        let risk: u32 = env.invoke_contract(&oracle, &Symbol::new(&env, "default_risk"), soroban_sdk::vec![&env]);
+        let nav = Self::checked_fair_price(&env, &oracle, &dob_token)?;
+        let risk: u32 = env.invoke_contract(
+            &oracle,
+            &Symbol::new(&env, "default_risk"),
+            (dob_token.clone(),).into_val(&env),
+        );
 
         // Calculate fee (same logic as request_quote)
         let fee_bps = if risk < 1500 {
@@ -219,6 +296,8 @@ impl LiquidNodeStabilizer {
     ) -> Result<i128, Error> {
         seller.require_auth();
 
+        Self::extend_instance(&env);
+
         if dob_amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -228,8 +307,12 @@ impl LiquidNodeStabilizer {
         let oracle: Address = env.storage().instance().get(&DataKey::Oracle).unwrap();
 
         // Get NAV and risk from oracle
-        let nav: i128 = env.invoke_contract(&oracle, &Symbol::new(&env, "fair_price"), soroban_sdk::vec![&env]);
-        let risk: u32 = env.invoke_contract(&oracle, &Symbol::new(&env, "default_risk"), soroban_sdk::vec![&env]);
+        let nav = Self::checked_fair_price(&env, &oracle, &dob_token)?;
+        let risk: u32 = env.invoke_contract(
+            &oracle,
+            &Symbol::new(&env, "default_risk"),
+            (dob_token.clone(),).into_val(&env),
+        );
 
         // Calculate fee
         let fee_bps = if risk < 1500 {
@@ -279,11 +362,16 @@ impl LiquidNodeStabilizer {
     }
 
     /// Get quote for direct liquidity provision
-    pub fn quote_liquidity_direct(env: Env, dob_amount: i128) -> (i128, u32) {
+    pub fn quote_liquidity_direct(env: Env, dob_amount: i128) -> Result<(i128, u32), Error> {
         let oracle: Address = env.storage().instance().get(&DataKey::Oracle).unwrap();
+        let dob_token: Address = env.storage().instance().get(&DataKey::DobToken).unwrap();
 
-        let nav: i128 = env.invoke_contract(&oracle, &Symbol::new(&env, "fair_price"), soroban_sdk::vec![&env]);
-        let risk: u32 = env.invoke_contract(&oracle, &Symbol::new(&env, "default_risk"), soroban_sdk::vec![&env]);
+        let nav = Self::checked_fair_price(&env, &oracle, &dob_token)?;
+        let risk: u32 = env.invoke_contract(
+            &oracle,
+            &Symbol::new(&env, "default_risk"),
+            (dob_token.clone(),).into_val(&env),
+        );
 
         let fee_bps = if risk < 1500 {
             500
@@ -298,7 +386,98 @@ impl LiquidNodeStabilizer {
         let value = (dob_amount * nav) / 10_000_000;
         let usdc_provided = (value * (BPS - fee_bps) as i128) / BPS as i128;
 
-        (usdc_provided, fee_bps)
+        Ok((usdc_provided, fee_bps))
+    }
+
+    /// Set the maximum oracle price age accepted for liquidity (operator only)
+    pub fn set_heartbeat(env: Env, max_age_secs: u64) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        env.storage().instance().set(&DataKey::Heartbeat, &max_age_secs);
+
+        env.events()
+            .publish((Symbol::new(&env, "heartbeat_set"),), max_age_secs);
+
+        Ok(())
+    }
+
+    /// Get the maximum oracle price age accepted for liquidity
+    pub fn heartbeat(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Heartbeat)
+            .unwrap_or(DEFAULT_HEARTBEAT)
+    }
+
+    /// Propose a new operator, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (operator only)
+    pub fn propose_operator(env: Env, new_operator: Address, expires_at: Option<u64>) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let proposal = OperatorProposal {
+            proposed: new_operator.clone(),
+            expires_at,
+        };
+        env.storage().instance().set(&DataKey::PendingOperator, &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_proposed"), operator),
+            proposal,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending operator proposal (only the proposed address)
+    pub fn accept_operator(env: Env) -> Result<(), Error> {
+        let proposal = Self::pending_operator(env.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
+
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let previous: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        env.storage().instance().set(&DataKey::Operator, &proposal.proposed);
+        env.storage().instance().remove(&DataKey::PendingOperator);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_changed"), previous),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the pending operator proposal (operator only)
+    pub fn cancel_proposal(env: Env) -> Result<(), Error> {
+        let operator: Address = env.storage().instance().get(&DataKey::Operator).unwrap();
+        operator.require_auth();
+
+        let proposal = Self::pending_operator(env.clone()).ok_or(Error::NoProposal)?;
+        env.storage().instance().remove(&DataKey::PendingOperator);
+
+        env.events().publish(
+            (Symbol::new(&env, "operator_proposal_cancelled"), operator),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Get the pending operator proposal, if any
+    pub fn pending_operator(env: Env) -> Option<OperatorProposal> {
+        env.storage().instance().get(&DataKey::PendingOperator)
     }
 
     /// Withdraw accumulated fees (operator only)
@@ -380,11 +559,285 @@ impl LiquidNodeStabilizer {
 
         Ok(())
     }
+
+    /// Get admin address
+    pub fn admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
+
+    /// Propose a new admin, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (only current admin)
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: Option<u64>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let proposal = AdminProposal {
+            proposed: new_admin.clone(),
+            expires_at,
+        };
+        env.storage().instance().set(&DataKey::PendingAdmin, &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposed"), admin),
+            proposal,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a pending admin proposal (only the proposed address)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
+
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let previous = Self::admin(env.clone());
+        env.storage().instance().set(&DataKey::Admin, &proposal.proposed);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_changed"), previous),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the pending admin proposal (only current admin)
+    pub fn cancel_admin_proposal(env: Env) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposal_cancelled"), admin),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Get the pending admin proposal, if any
+    pub fn pending_admin(env: Env) -> Option<AdminProposal> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    /// Set the storage TTL thresholds (only admin)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if config.instance_threshold > config.instance_extend_to
+            || config.instance_extend_to > env.storage().max_ttl()
+        {
+            return Err(Error::InvalidTtl);
+        }
+
+        env.storage().instance().set(&DataKey::TtlConfig, &config);
+        Self::extend_instance(&env);
+
+        env.events().publish(
+            (Symbol::new(&env, "ttl_config_set"), admin),
+            config,
+        );
+
+        Ok(())
+    }
+
+    /// Get the storage TTL thresholds
+    pub fn ttl_config(env: Env) -> TtlConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TtlConfig)
+            .unwrap_or(TtlConfig {
+                instance_threshold: INSTANCE_LIFETIME_THRESHOLD,
+                instance_extend_to: INSTANCE_BUMP_AMOUNT,
+            })
+    }
+
+    /// Replace the contract code (only admin)
+    /// Call `migrate` afterwards when the new code raises the schema version
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "upgraded"), admin),
+            new_wasm_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Apply storage layout changes up to the current schema version (only admin)
+    /// Contracts initialized before versioning report version 0
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let from_version = Self::schema_version(env.clone());
+        if from_version >= SCHEMA_VERSION {
+            return Err(Error::AlreadyMigrated);
+        }
+
+        // Version 1 introduced no layout change; later steps go here keyed on `from_version`
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+
+        env.events().publish(
+            (Symbol::new(&env, "migrated"), admin),
+            (from_version, SCHEMA_VERSION),
+        );
+
+        Ok(SCHEMA_VERSION)
+    }
+
+    /// Get the storage layout version
+    pub fn schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(0)
+    }
+}
+
+impl LiquidNodeStabilizer {
+    fn extend_instance(env: &Env) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .instance()
+            .extend_ttl(config.instance_threshold, config.instance_extend_to);
+    }
+
+    /// Read the oracle fair price, failing if it is older than the heartbeat
+    /// or the oracle is frozen
+    fn checked_fair_price(env: &Env, oracle: &Address, dob_token: &Address) -> Result<i128, Error> {
+        let heartbeat = Self::heartbeat(env.clone());
+        Ok(dob_oracle_client::fair_price_checked(env, oracle, dob_token, heartbeat)?)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use soroban_sdk::{
+        contract, contracterror, contractimpl, symbol_short,
+        testutils::{Address as _, Ledger},
+    };
+
+    /// Oracle stand-in whose feed can be marked stale or frozen
+    #[contract]
+    pub struct MockOracle;
+
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+    #[repr(u32)]
+    pub enum OracleError {
+        Stale = 6,
+        Paused = 21,
+    }
+
+    #[contractimpl]
+    impl MockOracle {
+        pub fn set_stale(env: Env, stale: bool) {
+            env.storage().instance().set(&symbol_short!("stale"), &stale);
+        }
+
+        pub fn set_paused(env: Env, paused: bool) {
+            env.storage().instance().set(&symbol_short!("paused"), &paused);
+        }
+
+        pub fn fair_price_checked(env: Env, _asset: Address, _max_age: u64) -> Result<i128, OracleError> {
+            if Self::flag(&env, symbol_short!("paused")) {
+                return Err(OracleError::Paused);
+            }
+            if Self::flag(&env, symbol_short!("stale")) {
+                return Err(OracleError::Stale);
+            }
+            Ok(10_000_000)
+        }
+
+        pub fn default_risk(_env: Env, _asset: Address) -> u32 {
+            1000
+        }
+    }
+
+    impl MockOracle {
+        fn flag(env: &Env, name: Symbol) -> bool {
+            env.storage().instance().get(&name).unwrap_or(false)
+        }
+    }
+
+    fn setup(env: &Env) -> (LiquidNodeStabilizerClient<'_>, MockOracleClient<'_>) {
+        let oracle = env.register_contract(None, MockOracle);
+        let usdc_token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        let contract_id = env.register_contract(None, LiquidNodeStabilizer);
+        let client = LiquidNodeStabilizerClient::new(env, &contract_id);
+
+        env.mock_all_auths();
+        client.initialize(
+            &Address::generate(env),
+            &oracle,
+            &usdc_token,
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+        );
+
+        (client, MockOracleClient::new(env, &oracle))
+    }
+
+    #[test]
+    fn test_stale_oracle_blocks_quotes() {
+        let env = Env::default();
+        let (client, oracle) = setup(&env);
+        let seller = Address::generate(&env);
+
+        // 1000 DOB at 1.00 with 10% risk: 5% fee
+        assert_eq!(client.quote_liquidity_direct(&1000_0000000), (950_0000000, 500));
+        // A live feed gets as far as the unfunded USDC buffer
+        assert_eq!(client.try_request_quote(&1000_0000000), Err(Ok(Error::InsufficientBalance)));
+
+        oracle.set_stale(&true);
+        assert_eq!(client.try_request_quote(&1000_0000000), Err(Ok(Error::StalePrice)));
+        assert_eq!(client.try_quote_liquidity_direct(&1000_0000000), Err(Ok(Error::StalePrice)));
+        assert_eq!(
+            client.try_execute_liquidity(&seller, &1000_0000000),
+            Err(Ok(Error::StalePrice))
+        );
+    }
+
+    #[test]
+    fn test_paused_oracle_blocks_quotes() {
+        let env = Env::default();
+        let (client, oracle) = setup(&env);
+
+        oracle.set_paused(&true);
+        assert_eq!(client.try_request_quote(&1000_0000000), Err(Ok(Error::OraclePaused)));
+        assert_eq!(client.try_quote_liquidity_direct(&1000_0000000), Err(Ok(Error::OraclePaused)));
+    }
 
     #[test]
     fn test_dynamic_fee_calculation() {
@@ -393,4 +846,73 @@ mod test {
         // Risk 35% (3500 bps) -> 20% fee (2000 bps)
         // Risk 60% (6000 bps) -> 30% fee (3000 bps)
     }
+
+    #[test]
+    fn test_operator_handover() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        let operator = client.get_addresses().3;
+        let new_operator = Address::generate(&env);
+
+        // Proposals must expire in the future and lapse once that passes
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        assert_eq!(
+            client.try_propose_operator(&new_operator, &Some(1_000)),
+            Err(Ok(Error::ProposalExpired))
+        );
+        client.propose_operator(&new_operator, &Some(2_000));
+        env.ledger().with_mut(|li| li.timestamp = 2_001);
+        assert_eq!(client.try_accept_operator(), Err(Ok(Error::ProposalExpired)));
+
+        client.cancel_proposal();
+        assert_eq!(env.auths()[0].0, operator);
+        assert_eq!(client.pending_operator(), None);
+        assert_eq!(client.try_accept_operator(), Err(Ok(Error::NoProposal)));
+        assert_eq!(client.try_cancel_proposal(), Err(Ok(Error::NoProposal)));
+
+        // The role only moves once the proposed address accepts
+        client.propose_operator(&new_operator, &None);
+        assert_eq!(client.get_addresses().3, operator);
+        client.accept_operator();
+        assert_eq!(env.auths()[0].0, new_operator);
+        assert_eq!(client.get_addresses().3, new_operator);
+        assert_eq!(client.pending_operator(), None);
+
+        client.set_heartbeat(&600);
+        assert_eq!(env.auths()[0].0, new_operator);
+    }
+
+    #[test]
+    fn test_admin_handover() {
+        let env = Env::default();
+        let (client, _) = setup(&env);
+        let admin = client.admin();
+        let new_admin = Address::generate(&env);
+
+        // Upgrade-level settings answer to the admin, not the operator
+        client.set_ttl_config(&client.ttl_config());
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(client.try_migrate(), Err(Ok(Error::AlreadyMigrated)));
+
+        // A lapsed proposal cannot be accepted
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        assert_eq!(
+            client.try_propose_admin(&new_admin, &Some(1_000)),
+            Err(Ok(Error::ProposalExpired))
+        );
+        client.propose_admin(&new_admin, &Some(2_000));
+        env.ledger().with_mut(|li| li.timestamp = 2_001);
+        assert_eq!(client.try_accept_admin(), Err(Ok(Error::ProposalExpired)));
+
+        client.cancel_admin_proposal();
+        assert_eq!(client.pending_admin(), None);
+        assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoProposal)));
+
+        client.propose_admin(&new_admin, &None);
+        assert_eq!(client.admin(), admin);
+        client.accept_admin();
+        assert_eq!(env.auths()[0].0, new_admin);
+        assert_eq!(client.admin(), new_admin);
+        assert_eq!(client.pending_admin(), None);
+    }
 }
@@ -1,19 +1,112 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, contracterror, Address, Env, String, Symbol};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, contracterror, token, Address, BytesN, Env, IntoVal,
+    InvokeError, String, Symbol, Vec,
+};
 
 /// Storage keys for the contract
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,           // Contract administrator
-    Hook,            // Hook contract that can mint/burn
+    Minters,         // Vec<Address> of accounts holding a minter role
+    Minter(Address), // MinterConfig of a minter
     Name,            // Token name
     Symbol,          // Token symbol
     Decimals,        // Token decimals
     TotalSupply,     // Total supply
     Balance(Address), // Balance of an address
-    Allowance(Address, Address), // Allowance from owner to spender
+    Allowance(Address, Address), // AllowanceValue from owner to spender
+    PendingAdmin,    // AdminProposal awaiting acceptance
+    ComplianceEnabled, // bool, fixed at initialization
+    Allowed(Address),  // KYC-verified holder (compliance mode)
+    Frozen(Address),   // Account barred from moving tokens (compliance mode)
+    TransferRestriction, // Optional contract consulted via can_transfer before transfers
+    CurrentSnapshot,   // Id of the latest snapshot (0 before the first)
+    BalanceCheckpoints(Address), // Vec<Checkpoint> of balances before post-snapshot writes
+    SupplyCheckpoints, // Vec<Checkpoint> of total supply before post-snapshot writes
+    Distributor,       // Optional revenue distributor notified before balance changes
+    Maturity,          // Timestamp after which no more tokens can be minted
+    MaxSupply,         // Hard cap on total supply
+    CoverageGuard,     // Oracle and minimum cashflow coverage checked on mint
+    RedemptionDesk,    // Operator settling redeem requests and the asset it pays in
+    TtlConfig,         // Storage TTL thresholds, defaults when unset
+    NextRedeemRequestId, // Id assigned to the next redeem request
+    RedeemRequest(u64),  // Pending redeem request, tokens held in escrow
+    SchemaVersion,       // Storage layout version applied by migrate()
+}
+
+/// Allowance amount with the last ledger it can be spent in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Value a balance or the total supply held at a snapshot, recorded on
+/// the first write after it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub snapshot_id: u32,
+    pub value: i128,
+}
+
+/// Cumulative mint allowance and burn permission of a minter
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinterConfig {
+    pub mint_cap: i128, // Total amount the minter may ever mint
+    pub minted: i128,   // Amount minted so far
+    pub can_burn: bool, // Whether the minter may burn holder tokens
+}
+
+/// Pending admin handover awaiting acceptance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposal {
+    pub proposed: Address,
+    pub expires_at: Option<u64>,
+}
+
+/// Oracle whose `present_value` must cover the supply after a mint by at
+/// least `min_coverage_bps` (10000 = 1x, one token = 1 USDC of principal)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoverageGuard {
+    pub oracle: Address,
+    pub min_coverage_bps: u32,
+}
+
+/// Operator settling redeem requests off-AMM and the asset it pays in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RedemptionDesk {
+    pub operator: Address,
+    pub settlement_token: Address,
+}
+
+/// Tokens a holder asked to redeem, held by this contract until settled
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RedeemRequest {
+    pub holder: Address,
+    pub amount: i128,
+    pub min_settlement: i128, // Least settlement token amount the holder accepts
+    pub requested_at: u64,
+}
+
+/// Ledgers before expiry at which entries are extended, and the TTL they are
+/// extended to; persistent values apply to balances and other holder entries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    pub instance_threshold: u32,
+    pub instance_extend_to: u32,
+    pub persistent_threshold: u32,
+    pub persistent_extend_to: u32,
 }
 
 /// Errors that can be returned by the contract
@@ -23,10 +116,37 @@ pub enum Error {
     Unauthorized = 1,
     InsufficientBalance = 2,
     InsufficientAllowance = 3,
+    NoProposal = 4,
+    ProposalExpired = 5,
+    InvalidAmount = 6,
+    InvalidExpiration = 7,
+    ComplianceDisabled = 8,
+    NotAllowlisted = 9,
+    AccountFrozen = 10,
+    TransferRestricted = 11,
+    InvalidSnapshot = 12,
+    Matured = 13,
+    InvalidMaturity = 14,
+    RedemptionsDisabled = 15,
+    UnknownRequest = 16,
+    MintCapExceeded = 17,
+    SupplyCapExceeded = 18,
+    CoverageTooLow = 19,
+    InvalidTtl = 20,
+    AlreadyMigrated = 21,
+    SettlementTooLow = 22,
 }
 
-/// DobToken - ERC20-like token for RWA revenue streams
-/// Only the hook contract can mint and burn tokens
+const BPS: u32 = 10000; // Basis points denominator
+const DAY_IN_LEDGERS: u32 = 17280; // ~5s per ledger
+const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const BALANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const BALANCE_LIFETIME_THRESHOLD: u32 = BALANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+const SCHEMA_VERSION: u32 = 1; // Bump together with a migrate() step
+
+/// DobToken - SEP-41 token for RWA revenue streams
+/// Only minters can mint and burn on behalf of holders
 #[contract]
 pub struct DobToken;
 
@@ -40,6 +160,7 @@ impl DobToken {
         name: String,
         symbol: String,
         decimals: u32,
+        compliance_enabled: bool,
     ) {
         if env.storage().instance().has(&DataKey::Admin) {
             panic!("Already initialized");
@@ -48,11 +169,23 @@ impl DobToken {
         admin.require_auth();
 
         env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::Hook, &hook);
+        env.storage().instance().set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        Self::write_minter(
+            &env,
+            &hook,
+            &MinterConfig {
+                mint_cap: i128::MAX,
+                minted: 0,
+                can_burn: true,
+            },
+        );
         env.storage().instance().set(&DataKey::Name, &name);
         env.storage().instance().set(&DataKey::Symbol, &symbol);
         env.storage().instance().set(&DataKey::Decimals, &decimals);
-        env.storage().instance().set(&DataKey::TotalSupply, &0i128);
+        Self::write_total_supply(&env, 0);
+        env.storage()
+            .instance()
+            .set(&DataKey::ComplianceEnabled, &compliance_enabled);
     }
 
     /// Get token name
@@ -89,10 +222,14 @@ impl DobToken {
 
     /// Get balance of an address
     pub fn balance(env: Env, account: Address) -> i128 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Balance(account))
-            .unwrap_or(0)
+        let key = DataKey::Balance(account);
+        match env.storage().persistent().get(&key) {
+            Some(balance) => {
+                Self::extend_persistent(&env, &key);
+                balance
+            }
+            None => 0,
+        }
     }
 
     /// Transfer tokens
@@ -103,6 +240,10 @@ impl DobToken {
             return Err(Error::InsufficientBalance);
         }
 
+        Self::check_not_frozen(&env, &from)?;
+        Self::check_receiver(&env, &to)?;
+        Self::check_transfer_restriction(&env, &from, &to, amount)?;
+
         let from_balance = Self::balance(env.clone(), from.clone());
         if from_balance < amount {
             return Err(Error::InsufficientBalance);
@@ -110,12 +251,8 @@ impl DobToken {
 
         let to_balance = Self::balance(env.clone(), to.clone());
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Balance(from.clone()), &(from_balance - amount));
-        env.storage()
-            .persistent()
-            .set(&DataKey::Balance(to.clone()), &(to_balance + amount));
+        Self::write_balance(&env, &from, from_balance - amount);
+        Self::write_balance(&env, &to, to_balance + amount);
 
         env.events().publish(
             (Symbol::new(&env, "transfer"), from, to),
@@ -125,26 +262,55 @@ impl DobToken {
         Ok(())
     }
 
-    /// Approve spender
-    pub fn approve(env: Env, owner: Address, spender: Address, amount: i128) {
-        owner.require_auth();
+    /// Approve spender until `expiration_ledger` (inclusive)
+    /// A non-zero amount needs an expiration ledger no earlier than the current one
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), Error> {
+        from.require_auth();
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Allowance(owner.clone(), spender.clone()), &amount);
+        if amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            return Err(Error::InvalidExpiration);
+        }
+
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().persistent().set(
+            &key,
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+        Self::extend_persistent(&env, &key);
 
         env.events().publish(
-            (Symbol::new(&env, "approve"), owner, spender),
-            amount,
+            (Symbol::new(&env, "approve"), from, spender),
+            (amount, expiration_ledger),
         );
+
+        Ok(())
     }
 
-    /// Get allowance
-    pub fn allowance(env: Env, owner: Address, spender: Address) -> i128 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Allowance(owner, spender))
-            .unwrap_or(0)
+    /// Get allowance, zero once expired
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        let key = DataKey::Allowance(from, spender);
+        let allowance: Option<AllowanceValue> = env.storage().persistent().get(&key);
+        if allowance.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+
+        match allowance {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance.amount,
+            _ => 0,
+        }
     }
 
     /// Transfer from (with allowance)
@@ -161,33 +327,22 @@ impl DobToken {
             return Err(Error::InsufficientBalance);
         }
 
-        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
-        if allowance < amount {
-            return Err(Error::InsufficientAllowance);
-        }
+        Self::check_not_frozen(&env, &from)?;
+        Self::check_receiver(&env, &to)?;
+        Self::check_transfer_restriction(&env, &from, &to, amount)?;
 
         let from_balance = Self::balance(env.clone(), from.clone());
         if from_balance < amount {
             return Err(Error::InsufficientBalance);
         }
 
+        Self::spend_allowance(&env, &from, &spender, amount)?;
+
         let to_balance = Self::balance(env.clone(), to.clone());
 
         // Update balances
-        env.storage()
-            .persistent()
-            .set(&DataKey::Balance(from.clone()), &(from_balance - amount));
-        env.storage()
-            .persistent()
-            .set(&DataKey::Balance(to.clone()), &(to_balance + amount));
-
-        // Update allowance
-        env.storage()
-            .persistent()
-            .set(
-                &DataKey::Allowance(from.clone(), spender.clone()),
-                &(allowance - amount),
-            );
+        Self::write_balance(&env, &from, from_balance - amount);
+        Self::write_balance(&env, &to, to_balance + amount);
 
         env.events().publish(
             (Symbol::new(&env, "transfer"), from, to),
@@ -197,29 +352,34 @@ impl DobToken {
         Ok(())
     }
 
-    /// Mint new tokens (only callable by hook)
-    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), Error> {
-        let hook: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Hook)
-            .expect("Hook not set");
-
-        hook.require_auth();
+    /// Mint new tokens against the minter's cap (only minters)
+    pub fn mint(env: Env, minter: Address, to: Address, amount: i128) -> Result<(), Error> {
+        minter.require_auth();
 
         if amount < 0 {
-            panic!("Invalid amount");
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut config = Self::minter_config(env.clone(), minter.clone()).ok_or(Error::Unauthorized)?;
+        if amount > config.mint_cap - config.minted {
+            return Err(Error::MintCapExceeded);
+        }
+
+        if Self::is_matured(env.clone()) {
+            return Err(Error::Matured);
         }
 
+        Self::check_receiver(&env, &to)?;
+        Self::check_issuance_ceiling(&env, amount)?;
+
+        config.minted += amount;
+        Self::write_minter(&env, &minter, &config);
+
         let to_balance = Self::balance(env.clone(), to.clone());
         let total_supply = Self::total_supply(env.clone());
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Balance(to.clone()), &(to_balance + amount));
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalSupply, &(total_supply + amount));
+        Self::write_balance(&env, &to, to_balance + amount);
+        Self::write_total_supply(&env, total_supply + amount);
 
         env.events().publish(
             (Symbol::new(&env, "mint"), to),
@@ -229,48 +389,119 @@ impl DobToken {
         Ok(())
     }
 
-    /// Burn tokens from an address (only callable by hook)
+    /// Burn tokens from an address (only minters with burn permission)
+    pub fn minter_burn(env: Env, minter: Address, from: Address, amount: i128) -> Result<(), Error> {
+        minter.require_auth();
+
+        match Self::minter_config(env.clone(), minter) {
+            Some(config) if config.can_burn => {}
+            _ => return Err(Error::Unauthorized),
+        }
+
+        if amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::check_not_frozen(&env, &from)?;
+        Self::burn_balance(&env, &from, amount)
+    }
+
+    /// Burn the caller's own tokens (SEP-41)
     pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), Error> {
-        let hook: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Hook)
-            .expect("Hook not set");
+        from.require_auth();
+
+        if amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::check_not_frozen(&env, &from)?;
+        Self::burn_balance(&env, &from, amount)
+    }
 
-        hook.require_auth();
+    /// Burn tokens from `from` against the spender's allowance (SEP-41)
+    pub fn burn_from(env: Env, spender: Address, from: Address, amount: i128) -> Result<(), Error> {
+        spender.require_auth();
 
         if amount < 0 {
-            panic!("Invalid amount");
+            return Err(Error::InvalidAmount);
         }
 
-        let from_balance = Self::balance(env.clone(), from.clone());
-        if from_balance < amount {
+        Self::check_not_frozen(&env, &from)?;
+
+        if Self::balance(env.clone(), from.clone()) < amount {
             return Err(Error::InsufficientBalance);
         }
 
-        let total_supply = Self::total_supply(env.clone());
+        Self::spend_allowance(&env, &from, &spender, amount)?;
+        Self::burn_balance(&env, &from, amount)
+    }
+
+    /// Grant or update a minter role (only admin)
+    /// `mint_cap` is cumulative; amounts already minted count against it
+    pub fn set_minter(env: Env, minter: Address, mint_cap: i128, can_burn: bool) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if mint_cap < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let minted = Self::minter_config(env.clone(), minter.clone())
+            .map(|config| config.minted)
+            .unwrap_or(0);
+        let config = MinterConfig {
+            mint_cap,
+            minted,
+            can_burn,
+        };
+        Self::write_minter(&env, &minter, &config);
+
+        env.events().publish(
+            (Symbol::new(&env, "minter_set"), admin, minter),
+            config,
+        );
+
+        Ok(())
+    }
+
+    /// Revoke a minter role (only admin)
+    pub fn remove_minter(env: Env, minter: Address) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if Self::minter_config(env.clone(), minter.clone()).is_none() {
+            return Err(Error::Unauthorized);
+        }
 
         env.storage()
             .persistent()
-            .set(&DataKey::Balance(from.clone()), &(from_balance - amount));
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalSupply, &(total_supply - amount));
+            .remove(&DataKey::Minter(minter.clone()));
+
+        let mut minters = Self::minters(env.clone());
+        if let Some(index) = minters.first_index_of(&minter) {
+            minters.remove(index);
+        }
+        env.storage().instance().set(&DataKey::Minters, &minters);
 
         env.events().publish(
-            (Symbol::new(&env, "burn"), from),
-            amount,
+            (Symbol::new(&env, "minter_removed"), admin, minter),
+            (),
         );
 
         Ok(())
     }
 
-    /// Get hook address
-    pub fn hook(env: Env) -> Address {
+    /// Get a minter's cap, minted amount and burn permission
+    pub fn minter_config(env: Env, minter: Address) -> Option<MinterConfig> {
+        env.storage().persistent().get(&DataKey::Minter(minter))
+    }
+
+    /// Get all minters
+    pub fn minters(env: Env) -> Vec<Address> {
         env.storage()
             .instance()
-            .get(&DataKey::Hook)
-            .expect("Hook not set")
+            .get(&DataKey::Minters)
+            .unwrap_or(Vec::new(&env))
     }
 
     /// Get admin address
@@ -281,111 +512,1570 @@ impl DobToken {
             .expect("Admin not set")
     }
 
-    /// Update hook address (only admin)
-    pub fn set_hook(env: Env, new_hook: Address) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
+    /// Propose a new admin, who must accept before the role moves
+    /// An optional `expires_at` ledger timestamp bounds the proposal (only current admin)
+    pub fn propose_admin(env: Env, new_admin: Address, expires_at: Option<u64>) -> Result<(), Error> {
+        let admin: Address = Self::admin(env.clone());
+        admin.require_auth();
+
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let proposal = AdminProposal {
+            proposed: new_admin.clone(),
+            expires_at,
+        };
+        env.storage().instance().set(&DataKey::PendingAdmin, &proposal);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposed"), admin),
+            proposal,
+        );
+
+        Ok(())
+    }
 
+    /// Accept a pending admin proposal (only the proposed address)
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        proposal.proposed.require_auth();
+
+        if let Some(expires_at) = proposal.expires_at {
+            if env.ledger().timestamp() > expires_at {
+                return Err(Error::ProposalExpired);
+            }
+        }
+
+        let previous: Address = Self::admin(env.clone());
+        env.storage().instance().set(&DataKey::Admin, &proposal.proposed);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_changed"), previous),
+            proposal.proposed,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw the pending admin proposal (only current admin)
+    pub fn cancel_proposal(env: Env) -> Result<(), Error> {
+        let admin: Address = Self::admin(env.clone());
         admin.require_auth();
 
-        env.storage().instance().set(&DataKey::Hook, &new_hook);
+        let proposal = Self::pending_admin(env.clone()).ok_or(Error::NoProposal)?;
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        env.events().publish(
+            (Symbol::new(&env, "admin_proposal_cancelled"), admin),
+            proposal.proposed,
+        );
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
+    /// Get the pending admin proposal, if any
+    pub fn pending_admin(env: Env) -> Option<AdminProposal> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
 
-    #[test]
-    fn test_initialize() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, DobToken);
-        let client = DobTokenClient::new(&env, &contract_id);
+    /// Whether compliance mode was enabled at initialization
+    pub fn compliance_enabled(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::ComplianceEnabled)
+            .unwrap_or(false)
+    }
 
-        let admin = Address::generate(&env);
-        let hook = Address::generate(&env);
+    /// Add or remove an account from the KYC allowlist (only admin, compliance mode)
+    pub fn set_allowed(env: Env, account: Address, allowed: bool) -> Result<(), Error> {
+        let admin = Self::compliance_admin(&env)?;
 
-        env.mock_all_auths();
+        let key = DataKey::Allowed(account.clone());
+        env.storage().persistent().set(&key, &allowed);
+        Self::extend_persistent(&env, &key);
 
-        client.initialize(
-            &admin,
-            &hook,
-            &String::from_str(&env, "Dob Solar Farm 2035"),
-            &String::from_str(&env, "DOB-35"),
-            &7,
+        env.events().publish(
+            (Symbol::new(&env, "set_allowed"), admin, account),
+            allowed,
         );
 
-        assert_eq!(client.name(), String::from_str(&env, "Dob Solar Farm 2035"));
-        assert_eq!(client.symbol(), String::from_str(&env, "DOB-35"));
-        assert_eq!(client.decimals(), 7);
-        assert_eq!(client.total_supply(), 0);
+        Ok(())
     }
 
-    #[test]
-    fn test_mint_and_burn() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, DobToken);
-        let client = DobTokenClient::new(&env, &contract_id);
+    /// Whether an account is on the KYC allowlist
+    pub fn is_allowed(env: Env, account: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Allowed(account))
+            .unwrap_or(false)
+    }
 
-        let admin = Address::generate(&env);
-        let hook = Address::generate(&env);
-        let user = Address::generate(&env);
+    /// Freeze or unfreeze an account (only admin, compliance mode)
+    /// Frozen accounts cannot send, receive or burn tokens
+    pub fn set_frozen(env: Env, account: Address, frozen: bool) -> Result<(), Error> {
+        let admin = Self::compliance_admin(&env)?;
 
-        env.mock_all_auths();
+        let key = DataKey::Frozen(account.clone());
+        env.storage().persistent().set(&key, &frozen);
+        Self::extend_persistent(&env, &key);
 
-        client.initialize(
-            &admin,
-            &hook,
-            &String::from_str(&env, "DOB Token"),
-            &String::from_str(&env, "DOB"),
-            &7,
+        env.events().publish(
+            (Symbol::new(&env, "set_frozen"), admin, account),
+            frozen,
         );
 
-        // Mint tokens
-        client.mint(&user, &1000);
-        assert_eq!(client.balance(&user), 1000);
-        assert_eq!(client.total_supply(), 1000);
+        Ok(())
+    }
 
-        // Burn tokens
-        client.burn(&user, &300);
-        assert_eq!(client.balance(&user), 700);
-        assert_eq!(client.total_supply(), 700);
+    /// Whether an account is frozen
+    pub fn is_frozen(env: Env, account: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Frozen(account))
+            .unwrap_or(false)
     }
 
-    #[test]
-    fn test_transfer() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, DobToken);
-        let client = DobTokenClient::new(&env, &contract_id);
+    /// Remove tokens from an account, frozen or not (only admin, compliance mode)
+    pub fn clawback(env: Env, from: Address, amount: i128) -> Result<(), Error> {
+        let admin = Self::compliance_admin(&env)?;
 
-        let admin = Address::generate(&env);
-        let hook = Address::generate(&env);
-        let user1 = Address::generate(&env);
-        let user2 = Address::generate(&env);
+        if amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
 
-        env.mock_all_auths();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
 
-        client.initialize(
-            &admin,
-            &hook,
-            &String::from_str(&env, "DOB Token"),
-            &String::from_str(&env, "DOB"),
-            &7,
+        let total_supply = Self::total_supply(env.clone());
+
+        Self::write_balance(&env, &from, from_balance - amount);
+        Self::write_total_supply(&env, total_supply - amount);
+
+        env.events().publish(
+            (Symbol::new(&env, "clawback"), admin, from),
+            amount,
         );
 
-        // Mint to user1
-        client.mint(&user1, &1000);
+        Ok(())
+    }
 
-        // Transfer from user1 to user2
-        client.transfer(&user1, &user2, &400);
+    /// Set or clear the contract whose `can_transfer(from, to, amount)` must
+    /// succeed before every transfer (only admin)
+    pub fn set_transfer_restriction(env: Env, restriction: Option<Address>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
 
-        assert_eq!(client.balance(&user1), 600);
-        assert_eq!(client.balance(&user2), 400);
+        match &restriction {
+            Some(restriction) => env
+                .storage()
+                .instance()
+                .set(&DataKey::TransferRestriction, restriction),
+            None => env.storage().instance().remove(&DataKey::TransferRestriction),
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "transfer_restriction_set"), admin),
+            restriction,
+        );
+
+        Ok(())
+    }
+
+    /// Get the transfer restriction contract, if any
+    pub fn transfer_restriction(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::TransferRestriction)
+    }
+
+    /// Set the maturity timestamp, after which minting stops (only admin)
+    /// Can be moved while in the future, but not once reached
+    pub fn set_maturity(env: Env, maturity: u64) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if Self::is_matured(env.clone()) {
+            return Err(Error::Matured);
+        }
+
+        if maturity <= env.ledger().timestamp() {
+            return Err(Error::InvalidMaturity);
+        }
+
+        env.storage().instance().set(&DataKey::Maturity, &maturity);
+
+        env.events()
+            .publish((Symbol::new(&env, "maturity_set"), admin), maturity);
+
+        Ok(())
+    }
+
+    /// Get the maturity timestamp, if set
+    pub fn maturity(env: Env) -> Option<u64> {
+        env.storage().instance().get(&DataKey::Maturity)
+    }
+
+    /// Whether the maturity timestamp has been reached
+    pub fn is_matured(env: Env) -> bool {
+        match Self::maturity(env.clone()) {
+            Some(maturity) => env.ledger().timestamp() >= maturity,
+            None => false,
+        }
+    }
+
+    /// Set or clear the hard cap on total supply (only admin)
+    pub fn set_max_supply(env: Env, max_supply: Option<i128>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        match max_supply {
+            Some(max_supply) => {
+                if max_supply < 0 {
+                    return Err(Error::InvalidAmount);
+                }
+                env.storage().instance().set(&DataKey::MaxSupply, &max_supply);
+            }
+            None => env.storage().instance().remove(&DataKey::MaxSupply),
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "max_supply_set"), admin),
+            max_supply,
+        );
+
+        Ok(())
+    }
+
+    /// Get the hard cap on total supply, if any
+    pub fn max_supply(env: Env) -> Option<i128> {
+        env.storage().instance().get(&DataKey::MaxSupply)
+    }
+
+    /// Set or clear the oracle coverage check applied to mints (only admin)
+    pub fn set_coverage_guard(env: Env, guard: Option<CoverageGuard>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        match &guard {
+            Some(guard) => env.storage().instance().set(&DataKey::CoverageGuard, guard),
+            None => env.storage().instance().remove(&DataKey::CoverageGuard),
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "coverage_guard_set"), admin),
+            guard,
+        );
+
+        Ok(())
+    }
+
+    /// Get the coverage check applied to mints, if any
+    pub fn coverage_guard(env: Env) -> Option<CoverageGuard> {
+        env.storage().instance().get(&DataKey::CoverageGuard)
+    }
+
+    /// Set or clear the revenue distributor settled before every balance change (only admin)
+    pub fn set_distributor(env: Env, distributor: Option<Address>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        match &distributor {
+            Some(distributor) => env
+                .storage()
+                .instance()
+                .set(&DataKey::Distributor, distributor),
+            None => env.storage().instance().remove(&DataKey::Distributor),
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "distributor_set"), admin),
+            distributor,
+        );
+
+        Ok(())
+    }
+
+    /// Get the revenue distributor, if any
+    pub fn distributor(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Distributor)
+    }
+
+    /// Set the operator that settles redeem requests in `settlement_token` (only admin)
+    pub fn set_redemption_desk(env: Env, operator: Address, settlement_token: Address) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let desk = RedemptionDesk {
+            operator,
+            settlement_token,
+        };
+        env.storage().instance().set(&DataKey::RedemptionDesk, &desk);
+
+        env.events().publish(
+            (Symbol::new(&env, "redemption_desk_set"), admin),
+            desk,
+        );
+
+        Ok(())
+    }
+
+    /// Get the redemption desk, if configured
+    pub fn redemption_desk(env: Env) -> Option<RedemptionDesk> {
+        env.storage().instance().get(&DataKey::RedemptionDesk)
+    }
+
+    /// Lock tokens in escrow until the redemption operator settles or rejects,
+    /// or the holder cancels; settlements must pay at least `min_settlement`
+    /// The move into escrow is checked against the transfer restriction like a transfer
+    /// Returns the request id
+    pub fn request_redemption(env: Env, holder: Address, amount: i128, min_settlement: i128) -> Result<u64, Error> {
+        holder.require_auth();
+
+        if amount <= 0 || min_settlement <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::redemption_desk(env.clone()).ok_or(Error::RedemptionsDisabled)?;
+        Self::check_not_frozen(&env, &holder)?;
+
+        let holder_balance = Self::balance(env.clone(), holder.clone());
+        if holder_balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+
+        let escrow = env.current_contract_address();
+        Self::check_transfer_restriction(&env, &holder, &escrow, amount)?;
+
+        let escrow_balance = Self::balance(env.clone(), escrow.clone());
+        Self::write_balance(&env, &holder, holder_balance - amount);
+        Self::write_balance(&env, &escrow, escrow_balance + amount);
+
+        let request_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextRedeemRequestId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextRedeemRequestId, &(request_id + 1));
+        let key = DataKey::RedeemRequest(request_id);
+        env.storage().persistent().set(
+            &key,
+            &RedeemRequest {
+                holder: holder.clone(),
+                amount,
+                min_settlement,
+                requested_at: env.ledger().timestamp(),
+            },
+        );
+        Self::extend_persistent(&env, &key);
+
+        env.events().publish(
+            (Symbol::new(&env, "redeem_requested"), holder, request_id),
+            amount,
+        );
+
+        Ok(request_id)
+    }
+
+    /// Pay the holder `settlement_amount` and burn the escrowed tokens (only redemption operator)
+    /// Fails with `Error::SettlementTooLow` below the holder's minimum
+    pub fn settle_redemption(env: Env, request_id: u64, settlement_amount: i128) -> Result<(), Error> {
+        let desk = Self::redemption_desk(env.clone()).ok_or(Error::RedemptionsDisabled)?;
+        desk.operator.require_auth();
+
+        let request = Self::redeem_request(env.clone(), request_id).ok_or(Error::UnknownRequest)?;
+        if settlement_amount < request.min_settlement {
+            return Err(Error::SettlementTooLow);
+        }
+
+        let request = Self::take_redeem_request(&env, request_id)?;
+
+        token::Client::new(&env, &desk.settlement_token).transfer(
+            &desk.operator,
+            &request.holder,
+            &settlement_amount,
+        );
+        Self::burn_balance(&env, &env.current_contract_address(), request.amount)?;
+
+        env.events().publish(
+            (Symbol::new(&env, "redeem_settled"), request.holder, request_id),
+            (request.amount, settlement_amount),
+        );
+
+        Ok(())
+    }
+
+    /// Return the escrowed tokens to the holder (only redemption operator)
+    pub fn reject_redemption(env: Env, request_id: u64) -> Result<(), Error> {
+        let desk = Self::redemption_desk(env.clone()).ok_or(Error::RedemptionsDisabled)?;
+        desk.operator.require_auth();
+
+        let request = Self::take_redeem_request(&env, request_id)?;
+        Self::release_escrow(&env, &request);
+
+        env.events().publish(
+            (Symbol::new(&env, "redeem_rejected"), request.holder, request_id),
+            request.amount,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw a pending redeem request and get the escrowed tokens back (only its holder)
+    pub fn cancel_redemption(env: Env, request_id: u64) -> Result<(), Error> {
+        let request = Self::redeem_request(env.clone(), request_id).ok_or(Error::UnknownRequest)?;
+        request.holder.require_auth();
+
+        let request = Self::take_redeem_request(&env, request_id)?;
+        Self::release_escrow(&env, &request);
+
+        env.events().publish(
+            (Symbol::new(&env, "redeem_cancelled"), request.holder, request_id),
+            request.amount,
+        );
+
+        Ok(())
+    }
+
+    /// Get a pending redeem request
+    pub fn redeem_request(env: Env, request_id: u64) -> Option<RedeemRequest> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RedeemRequest(request_id))
+    }
+
+    /// Set the storage TTL thresholds (only admin)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        if config.instance_threshold > config.instance_extend_to
+            || config.persistent_threshold > config.persistent_extend_to
+            || config.instance_extend_to > env.storage().max_ttl()
+            || config.persistent_extend_to > env.storage().max_ttl()
+        {
+            return Err(Error::InvalidTtl);
+        }
+
+        env.storage().instance().set(&DataKey::TtlConfig, &config);
+        Self::extend_instance(&env);
+
+        env.events().publish(
+            (Symbol::new(&env, "ttl_config_set"), admin),
+            config,
+        );
+
+        Ok(())
+    }
+
+    /// Get the storage TTL thresholds
+    pub fn ttl_config(env: Env) -> TtlConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TtlConfig)
+            .unwrap_or(TtlConfig {
+                instance_threshold: INSTANCE_LIFETIME_THRESHOLD,
+                instance_extend_to: INSTANCE_BUMP_AMOUNT,
+                persistent_threshold: BALANCE_LIFETIME_THRESHOLD,
+                persistent_extend_to: BALANCE_BUMP_AMOUNT,
+            })
+    }
+
+    /// Extend the contract instance and an account's entries (anyone, e.g. a
+    /// keeper keeping long-lived holder balances from being archived)
+    pub fn bump(env: Env, account: Address) {
+        Self::extend_instance(&env);
+
+        for key in [
+            DataKey::Balance(account.clone()),
+            DataKey::BalanceCheckpoints(account.clone()),
+            DataKey::Allowed(account.clone()),
+            DataKey::Frozen(account),
+        ] {
+            if env.storage().persistent().has(&key) {
+                Self::extend_persistent(&env, &key);
+            }
+        }
+    }
+
+    /// Record balances and total supply at this point (only admin)
+    /// Returns the new snapshot id, starting at 1
+    pub fn snapshot(env: Env) -> u32 {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        Self::record_snapshot(&env)
+    }
+
+    /// Record a snapshot for a minter with burn permission, e.g. the primary
+    /// market fixing the holdings an amortization period retires
+    pub fn minter_snapshot(env: Env, minter: Address) -> Result<u32, Error> {
+        minter.require_auth();
+
+        match Self::minter_config(env.clone(), minter) {
+            Some(config) if config.can_burn => {}
+            _ => return Err(Error::Unauthorized),
+        }
+
+        Ok(Self::record_snapshot(&env))
+    }
+
+    /// Get the id of the latest snapshot (0 before the first)
+    pub fn current_snapshot(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CurrentSnapshot)
+            .unwrap_or(0)
+    }
+
+    /// Balance of an account at a snapshot
+    pub fn balance_of_at(env: Env, account: Address, snapshot_id: u32) -> Result<i128, Error> {
+        Self::check_snapshot_id(&env, snapshot_id)?;
+
+        let checkpoints = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BalanceCheckpoints(account.clone()));
+        Ok(Self::value_at(checkpoints, snapshot_id)
+            .unwrap_or_else(|| Self::balance(env, account)))
+    }
+
+    /// Total supply at a snapshot
+    pub fn total_supply_at(env: Env, snapshot_id: u32) -> Result<i128, Error> {
+        Self::check_snapshot_id(&env, snapshot_id)?;
+
+        let checkpoints = env.storage().persistent().get(&DataKey::SupplyCheckpoints);
+        Ok(Self::value_at(checkpoints, snapshot_id)
+            .unwrap_or_else(|| Self::total_supply(env)))
+    }
+
+    /// Replace the contract code (only admin)
+    /// Call `migrate` afterwards when the new code raises the schema version
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        env.events().publish(
+            (Symbol::new(&env, "upgraded"), admin),
+            new_wasm_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Apply storage layout changes up to the current schema version (only admin)
+    /// Contracts initialized before versioning report version 0
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+
+        let from_version = Self::schema_version(env.clone());
+        if from_version >= SCHEMA_VERSION {
+            return Err(Error::AlreadyMigrated);
+        }
+
+        // Version 1 introduced no layout change; later steps go here keyed on `from_version`
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+
+        env.events().publish(
+            (Symbol::new(&env, "migrated"), admin),
+            (from_version, SCHEMA_VERSION),
+        );
+
+        Ok(SCHEMA_VERSION)
+    }
+
+    /// Get the storage layout version
+    pub fn schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::SchemaVersion)
+            .unwrap_or(0)
+    }
+}
+
+impl DobToken {
+    /// Open a new snapshot and return its id
+    fn record_snapshot(env: &Env) -> u32 {
+        let snapshot_id = Self::current_snapshot(env.clone()) + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::CurrentSnapshot, &snapshot_id);
+
+        env.events().publish(
+            (Symbol::new(env, "snapshot"), snapshot_id),
+            env.ledger().timestamp(),
+        );
+
+        snapshot_id
+    }
+
+    /// Single write path for balances, checkpointing the old value on the
+    /// first change after a snapshot
+    fn write_balance(env: &Env, account: &Address, amount: i128) {
+        let current = Self::balance(env.clone(), account.clone());
+
+        let key = DataKey::BalanceCheckpoints(account.clone());
+        Self::checkpoint(env, &key, current);
+
+        // Settle revenue earned at the old balance; passing it in keeps the
+        // distributor from re-entering the token
+        let distributor: Option<Address> = env.storage().instance().get(&DataKey::Distributor);
+        if let Some(distributor) = distributor {
+            let _: () = env.invoke_contract(
+                &distributor,
+                &Symbol::new(env, "update_account"),
+                (account.clone(), current).into_val(env),
+            );
+        }
+
+        let key = DataKey::Balance(account.clone());
+        env.storage().persistent().set(&key, &amount);
+        Self::extend_persistent(env, &key);
+        Self::extend_instance(env);
+    }
+
+    /// Single write path for the total supply, see `write_balance`
+    fn write_total_supply(env: &Env, amount: i128) {
+        Self::checkpoint(env, &DataKey::SupplyCheckpoints, Self::total_supply(env.clone()));
+
+        env.storage().instance().set(&DataKey::TotalSupply, &amount);
+    }
+
+    fn checkpoint(env: &Env, key: &DataKey, current: i128) {
+        let snapshot_id = Self::current_snapshot(env.clone());
+        if snapshot_id == 0 {
+            return;
+        }
+
+        let mut checkpoints: Vec<Checkpoint> = env
+            .storage()
+            .persistent()
+            .get(key)
+            .unwrap_or(Vec::new(env));
+
+        let recorded = checkpoints
+            .last()
+            .map(|checkpoint| checkpoint.snapshot_id >= snapshot_id)
+            .unwrap_or(false);
+        if !recorded {
+            checkpoints.push_back(Checkpoint {
+                snapshot_id,
+                value: current,
+            });
+            env.storage().persistent().set(key, &checkpoints);
+            Self::extend_persistent(env, key);
+        }
+    }
+
+    /// Value at a snapshot: the first checkpoint taken at or after it, or
+    /// None if nothing changed since (the current value applies)
+    fn value_at(checkpoints: Option<Vec<Checkpoint>>, snapshot_id: u32) -> Option<i128> {
+        let checkpoints = checkpoints?;
+
+        // Checkpoints are ordered by snapshot id, binary search the first >= snapshot_id
+        let mut low = 0u32;
+        let mut high = checkpoints.len();
+        while low < high {
+            let mid = (low + high) / 2;
+            if checkpoints.get_unchecked(mid).snapshot_id < snapshot_id {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        checkpoints.get(low).map(|checkpoint| checkpoint.value)
+    }
+
+    fn check_snapshot_id(env: &Env, snapshot_id: u32) -> Result<(), Error> {
+        if snapshot_id == 0 || snapshot_id > Self::current_snapshot(env.clone()) {
+            return Err(Error::InvalidSnapshot);
+        }
+        Ok(())
+    }
+
+    /// Admin address after checking its auth and that compliance mode is on
+    fn compliance_admin(env: &Env) -> Result<Address, Error> {
+        if !Self::compliance_enabled(env.clone()) {
+            return Err(Error::ComplianceDisabled);
+        }
+
+        let admin = Self::admin(env.clone());
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Reject mints that would exceed the supply cap or dilute the oracle's
+    /// projected cashflow value below the configured coverage
+    fn check_issuance_ceiling(env: &Env, amount: i128) -> Result<(), Error> {
+        let new_supply = Self::total_supply(env.clone()) + amount;
+
+        if let Some(max_supply) = Self::max_supply(env.clone()) {
+            if new_supply > max_supply {
+                return Err(Error::SupplyCapExceeded);
+            }
+        }
+
+        if let Some(guard) = Self::coverage_guard(env.clone()) {
+            let present_value: i128 = env.invoke_contract(
+                &guard.oracle,
+                &Symbol::new(env, "present_value"),
+                (env.current_contract_address(),).into_val(env),
+            );
+            if present_value * (BPS as i128) < new_supply * guard.min_coverage_bps as i128 {
+                return Err(Error::CoverageTooLow);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_not_frozen(env: &Env, account: &Address) -> Result<(), Error> {
+        if Self::is_frozen(env.clone(), account.clone()) {
+            return Err(Error::AccountFrozen);
+        }
+        Ok(())
+    }
+
+    /// In compliance mode only allowlisted, unfrozen accounts can receive
+    fn check_receiver(env: &Env, to: &Address) -> Result<(), Error> {
+        Self::check_not_frozen(env, to)?;
+
+        if Self::compliance_enabled(env.clone()) && !Self::is_allowed(env.clone(), to.clone()) {
+            return Err(Error::NotAllowlisted);
+        }
+        Ok(())
+    }
+
+    /// Any error returned by the restriction contract rejects the transfer
+    fn check_transfer_restriction(env: &Env, from: &Address, to: &Address, amount: i128) -> Result<(), Error> {
+        let restriction: Option<Address> = env.storage().instance().get(&DataKey::TransferRestriction);
+        let Some(restriction) = restriction else {
+            return Ok(());
+        };
+
+        match env.try_invoke_contract::<(), InvokeError>(
+            &restriction,
+            &Symbol::new(env, "can_transfer"),
+            (from.clone(), to.clone(), amount).into_val(env),
+        ) {
+            Ok(Ok(())) => Ok(()),
+            Err(Ok(InvokeError::Contract(_))) => Err(Error::TransferRestricted),
+            _ => panic!("Transfer restriction call failed"),
+        }
+    }
+
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) -> Result<(), Error> {
+        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        if allowance < amount {
+            return Err(Error::InsufficientAllowance);
+        }
+
+        if amount > 0 {
+            let key = DataKey::Allowance(from.clone(), spender.clone());
+            let mut value: AllowanceValue = env.storage().persistent().get(&key).unwrap();
+            value.amount -= amount;
+            env.storage().persistent().set(&key, &value);
+            Self::extend_persistent(env, &key);
+        }
+
+        Ok(())
+    }
+
+    fn extend_instance(env: &Env) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .instance()
+            .extend_ttl(config.instance_threshold, config.instance_extend_to);
+    }
+
+    /// Extend an existing persistent entry
+    fn extend_persistent(env: &Env, key: &DataKey) {
+        let config = Self::ttl_config(env.clone());
+        env.storage()
+            .persistent()
+            .extend_ttl(key, config.persistent_threshold, config.persistent_extend_to);
+    }
+
+    fn write_minter(env: &Env, minter: &Address, config: &MinterConfig) {
+        let mut minters = Self::minters(env.clone());
+        if !minters.contains(minter) {
+            minters.push_back(minter.clone());
+            env.storage().instance().set(&DataKey::Minters, &minters);
+        }
+
+        let key = DataKey::Minter(minter.clone());
+        env.storage().persistent().set(&key, config);
+        Self::extend_persistent(env, &key);
+    }
+
+    fn take_redeem_request(env: &Env, request_id: u64) -> Result<RedeemRequest, Error> {
+        let key = DataKey::RedeemRequest(request_id);
+        let request: RedeemRequest = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::UnknownRequest)?;
+        env.storage().persistent().remove(&key);
+        Ok(request)
+    }
+
+    /// Move a request's escrowed tokens back to its holder
+    fn release_escrow(env: &Env, request: &RedeemRequest) {
+        let escrow = env.current_contract_address();
+        let escrow_balance = Self::balance(env.clone(), escrow.clone());
+        let holder_balance = Self::balance(env.clone(), request.holder.clone());
+        Self::write_balance(env, &escrow, escrow_balance - request.amount);
+        Self::write_balance(env, &request.holder, holder_balance + request.amount);
+    }
+
+    fn burn_balance(env: &Env, from: &Address, amount: i128) -> Result<(), Error> {
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            return Err(Error::InsufficientBalance);
+        }
+
+        let total_supply = Self::total_supply(env.clone());
+
+        Self::write_balance(env, from, from_balance - amount);
+        Self::write_total_supply(env, total_supply - amount);
+
+        env.events().publish(
+            (Symbol::new(env, "burn"), from.clone()),
+            amount,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{storage::Persistent as _, Address as _, Ledger};
+    use soroban_sdk::symbol_short;
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    #[contracterror]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum RestrictionError {
+        LockedUp = 1,
+    }
+
+    /// Restriction rule set that blocks senders under lockup
+    #[contract]
+    pub struct MockLockup;
+
+    #[contractimpl]
+    impl MockLockup {
+        pub fn lock(env: Env, account: Address) {
+            env.storage().instance().set(&account, &true);
+        }
+
+        pub fn can_transfer(env: Env, from: Address, _to: Address, _amount: i128) -> Result<(), RestrictionError> {
+            if env.storage().instance().has(&from) {
+                return Err(RestrictionError::LockedUp);
+            }
+            Ok(())
+        }
+    }
+
+    /// Valuation source reporting a fixed present value
+    #[contract]
+    pub struct MockValuation;
+
+    #[contractimpl]
+    impl MockValuation {
+        pub fn set_present_value(env: Env, value: i128) {
+            env.storage().instance().set(&symbol_short!("pv"), &value);
+        }
+
+        pub fn present_value(env: Env, _asset: Address) -> i128 {
+            env.storage().instance().get(&symbol_short!("pv")).unwrap_or(0)
+        }
+    }
+
+    #[test]
+    fn test_initialize() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "Dob Solar Farm 2035"),
+            &String::from_str(&env, "DOB-35"),
+            &7,
+            &false,
+        );
+
+        assert_eq!(client.name(), String::from_str(&env, "Dob Solar Farm 2035"));
+        assert_eq!(client.symbol(), String::from_str(&env, "DOB-35"));
+        assert_eq!(client.decimals(), 7);
+        assert_eq!(client.total_supply(), 0);
+    }
+
+    #[test]
+    fn test_mint_and_burn() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+
+        // Mint tokens
+        client.mint(&hook, &user, &1000);
+        assert_eq!(client.balance(&user), 1000);
+        assert_eq!(client.total_supply(), 1000);
+
+        // Burn tokens
+        client.minter_burn(&hook, &user, &300);
+        assert_eq!(client.balance(&user), 700);
+        assert_eq!(client.total_supply(), 700);
+
+        // Bad amounts come back as typed errors like the SEP-41 entry points
+        assert_eq!(client.try_mint(&hook, &user, &-1), Err(Ok(Error::InvalidAmount)));
+        assert_eq!(client.try_minter_burn(&hook, &user, &-1), Err(Ok(Error::InvalidAmount)));
+        assert_eq!(
+            client.try_minter_burn(&hook, &user, &701),
+            Err(Ok(Error::InsufficientBalance))
+        );
+    }
+
+    #[test]
+    fn test_minters() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let market = Address::generate(&env);
+        let pool = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &market,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+        assert_eq!(client.minters().len(), 1);
+        assert_eq!(client.try_mint(&pool, &user, &100), Err(Ok(Error::Unauthorized)));
+
+        // Second minter with a cumulative cap and no burn permission
+        client.set_minter(&pool, &1000, &false);
+        client.mint(&pool, &user, &600);
+        assert_eq!(client.try_mint(&pool, &user, &500), Err(Ok(Error::MintCapExceeded)));
+        client.mint(&pool, &user, &400);
+        assert_eq!(client.minter_config(&pool).unwrap().minted, 1000);
+        assert_eq!(client.try_minter_burn(&pool, &user, &100), Err(Ok(Error::Unauthorized)));
+
+        // Raising the cap keeps the minted amount
+        client.set_minter(&pool, &1500, &true);
+        client.mint(&pool, &user, &500);
+        client.minter_burn(&pool, &user, &100);
+        client.mint(&market, &user, &100);
+        assert_eq!(client.balance(&user), 1500);
+
+        client.remove_minter(&pool);
+        assert_eq!(client.minters().len(), 1);
+        assert_eq!(client.try_mint(&pool, &user, &1), Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_capped_minters_share_series() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let market = Address::generate(&env);
+        let pool = Address::generate(&env);
+        let buyer = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &market,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+        client.set_minter(&market, &1000, &true);
+        client.set_minter(&pool, &500, &true);
+
+        // Both minters issue into one supply, each against its own cap
+        client.mint(&market, &buyer, &1000);
+        client.mint(&pool, &buyer, &400);
+        assert_eq!(client.balance(&buyer), 1400);
+        assert_eq!(client.total_supply(), 1400);
+        assert_eq!(client.minter_config(&market).unwrap().minted, 1000);
+        assert_eq!(client.minter_config(&pool).unwrap().minted, 400);
+
+        // One minter reaching its cap does not stop the other
+        assert_eq!(client.try_mint(&market, &buyer, &1), Err(Ok(Error::MintCapExceeded)));
+        assert_eq!(client.try_mint(&pool, &buyer, &101), Err(Ok(Error::MintCapExceeded)));
+        client.mint(&pool, &buyer, &100);
+        assert_eq!(client.total_supply(), 1500);
+    }
+
+    #[test]
+    fn test_transfer() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+
+        // Mint to user1
+        client.mint(&hook, &user1, &1000);
+
+        // Transfer from user1 to user2
+        client.transfer(&user1, &user2, &400);
+
+        assert_eq!(client.balance(&user1), 600);
+        assert_eq!(client.balance(&user2), 400);
+    }
+
+    #[test]
+    fn test_admin_handover() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let new_admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+
+        client.propose_admin(&new_admin, &None);
+        assert_eq!(client.admin(), admin);
+
+        client.accept_admin();
+        assert_eq!(client.admin(), new_admin);
+        assert_eq!(client.try_cancel_proposal(), Err(Ok(Error::NoProposal)));
+    }
+
+    #[test]
+    fn test_sep41_token_client() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let receiver = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+        client.mint(&hook, &owner, &1000);
+
+        env.ledger().with_mut(|li| li.sequence_number = 100);
+        assert_eq!(
+            client.try_approve(&owner, &spender, &500, &99),
+            Err(Ok(Error::InvalidExpiration))
+        );
+
+        token.approve(&owner, &spender, &500, &200);
+        assert_eq!(token.allowance(&owner, &spender), 500);
+
+        token.transfer_from(&spender, &owner, &receiver, &200);
+        token.burn_from(&spender, &owner, &100);
+        assert_eq!(token.allowance(&owner, &spender), 200);
+        assert_eq!(token.balance(&owner), 700);
+        assert_eq!(token.balance(&receiver), 200);
+
+        // Holders burn their own tokens
+        token.burn(&receiver, &50);
+        assert_eq!(client.total_supply(), 850);
+
+        // Allowances lapse after their expiration ledger
+        env.ledger().with_mut(|li| li.sequence_number = 201);
+        assert_eq!(token.allowance(&owner, &spender), 0);
+        assert_eq!(
+            client.try_transfer_from(&spender, &owner, &receiver, &1),
+            Err(Ok(Error::InsufficientAllowance))
+        );
+
+        assert_eq!(token.decimals(), 7);
+        assert_eq!(token.symbol(), String::from_str(&env, "DOB"));
+    }
+
+    #[test]
+    fn test_compliance_mode() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &true,
+        );
+        assert!(client.compliance_enabled());
+
+        // Minting and transfers only reach allowlisted accounts
+        assert_eq!(client.try_mint(&hook, &alice, &1000), Err(Ok(Error::NotAllowlisted)));
+        client.set_allowed(&alice, &true);
+        client.mint(&hook, &alice, &1000);
+        assert_eq!(client.try_transfer(&alice, &bob, &100), Err(Ok(Error::NotAllowlisted)));
+        client.set_allowed(&bob, &true);
+        client.transfer(&alice, &bob, &100);
+
+        // Frozen accounts can neither send nor receive
+        client.set_frozen(&alice, &true);
+        assert_eq!(client.try_transfer(&alice, &bob, &100), Err(Ok(Error::AccountFrozen)));
+        assert_eq!(client.try_transfer(&bob, &alice, &100), Err(Ok(Error::AccountFrozen)));
+        assert_eq!(client.try_burn(&alice, &100), Err(Ok(Error::AccountFrozen)));
+
+        // Clawback still works on a frozen account
+        client.clawback(&alice, &400);
+        assert_eq!(client.balance(&alice), 500);
+        assert_eq!(client.total_supply(), 600);
+
+        client.set_frozen(&alice, &false);
+        client.transfer(&alice, &bob, &100);
+        assert_eq!(client.balance(&bob), 200);
+    }
+
+    #[test]
+    fn test_compliance_disabled() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+
+        client.mint(&hook, &user, &1000);
+        assert_eq!(client.try_set_frozen(&user, &true), Err(Ok(Error::ComplianceDisabled)));
+        assert_eq!(client.try_clawback(&user, &1000), Err(Ok(Error::ComplianceDisabled)));
+    }
+
+    #[test]
+    fn test_transfer_restriction() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+        let lockup_id = env.register_contract(None, MockLockup);
+        let lockup = MockLockupClient::new(&env, &lockup_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+        client.mint(&hook, &alice, &1000);
+
+        client.set_transfer_restriction(&Some(lockup_id.clone()));
+        client.transfer(&alice, &bob, &100);
+
+        lockup.lock(&alice);
+        assert_eq!(client.try_transfer(&alice, &bob, &100), Err(Ok(Error::TransferRestricted)));
+
+        env.ledger().with_mut(|li| li.sequence_number = 10);
+        client.approve(&alice, &bob, &100, &20);
+        assert_eq!(
+            client.try_transfer_from(&bob, &alice, &bob, &100),
+            Err(Ok(Error::TransferRestricted))
+        );
+
+        // A redeem request cannot move locked tokens into escrow either
+        client.set_redemption_desk(&admin, &Address::generate(&env));
+        assert_eq!(
+            client.try_request_redemption(&alice, &100, &1),
+            Err(Ok(Error::TransferRestricted))
+        );
+
+        // Mints and hook burns are not subject to the rule set
+        client.mint(&hook, &alice, &100);
+        client.minter_burn(&hook, &alice, &100);
+
+        client.set_transfer_restriction(&None);
+        client.transfer(&alice, &bob, &100);
+        assert_eq!(client.balance(&bob), 200);
+    }
+
+    #[test]
+    fn test_snapshots() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+        client.mint(&hook, &alice, &1000);
+        assert_eq!(client.try_balance_of_at(&alice, &1), Err(Ok(Error::InvalidSnapshot)));
+
+        let first = client.snapshot();
+        assert_eq!(first, 1);
+
+        // Transfers around the record date do not change the recorded balances
+        client.transfer(&alice, &bob, &400);
+        client.transfer(&alice, &bob, &100);
+        client.mint(&hook, &bob, &500);
+
+        let second = client.snapshot();
+        let third = client.snapshot();
+        client.minter_burn(&hook, &bob, &200);
+
+        assert_eq!(client.balance_of_at(&alice, &first), 1000);
+        assert_eq!(client.balance_of_at(&bob, &first), 0);
+        assert_eq!(client.total_supply_at(&first), 1000);
+
+        assert_eq!(client.balance_of_at(&alice, &second), 500);
+        assert_eq!(client.balance_of_at(&bob, &second), 1000);
+        assert_eq!(client.balance_of_at(&bob, &third), 1000);
+        assert_eq!(client.total_supply_at(&third), 1500);
+
+        assert_eq!(client.balance(&bob), 800);
+        assert_eq!(client.try_total_supply_at(&4), Err(Ok(Error::InvalidSnapshot)));
+
+        // Minters with burn permission can take snapshots too
+        assert_eq!(client.try_minter_snapshot(&alice), Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.minter_snapshot(&hook), 4);
+        assert_eq!(client.balance_of_at(&bob, &4), 800);
+    }
+
+    #[test]
+    fn test_maturity_stops_minting() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+        assert_eq!(client.try_set_maturity(&1_000), Err(Ok(Error::InvalidMaturity)));
+        client.set_maturity(&2_000);
+        client.mint(&hook, &user, &1000);
+
+        env.ledger().with_mut(|li| li.timestamp = 2_000);
+        assert!(client.is_matured());
+        assert_eq!(client.try_mint(&hook, &user, &1000), Err(Ok(Error::Matured)));
+        assert_eq!(client.try_set_maturity(&3_000), Err(Ok(Error::Matured)));
+
+        // Holders can still move and redeem matured tokens
+        client.minter_burn(&hook, &user, &400);
+        assert_eq!(client.balance(&user), 600);
+    }
+
+    #[test]
+    fn test_redeem_requests() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let operator = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+        client.mint(&hook, &user, &1000);
+
+        assert_eq!(client.try_request_redemption(&user, &100, &95), Err(Ok(Error::RedemptionsDisabled)));
+
+        let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        StellarAssetClient::new(&env, &usdc_id).mint(&operator, &10_000);
+        client.set_redemption_desk(&operator, &usdc_id);
+
+        // A minimum is required, so a zero settlement can never burn the escrow
+        assert_eq!(client.try_request_redemption(&user, &400, &0), Err(Ok(Error::InvalidAmount)));
+
+        // Settled requests burn the escrow and pay the holder at least their minimum
+        let settled = client.request_redemption(&user, &400, &380);
+        assert_eq!(client.balance(&user), 600);
+        assert_eq!(client.balance(&contract_id), 400);
+        assert_eq!(client.try_settle_redemption(&settled, &0), Err(Ok(Error::SettlementTooLow)));
+        assert_eq!(client.try_settle_redemption(&settled, &379), Err(Ok(Error::SettlementTooLow)));
+        client.settle_redemption(&settled, &380);
+        assert_eq!(client.balance(&contract_id), 0);
+        assert_eq!(client.total_supply(), 600);
+        assert_eq!(TokenClient::new(&env, &usdc_id).balance(&user), 380);
+        assert_eq!(client.try_settle_redemption(&settled, &380), Err(Ok(Error::UnknownRequest)));
+
+        // Rejected requests return the escrow
+        let rejected = client.request_redemption(&user, &200, &190);
+        assert_eq!(client.redeem_request(&rejected).unwrap().amount, 200);
+        client.reject_redemption(&rejected);
+        assert_eq!(client.balance(&user), 600);
+        assert_eq!(client.redeem_request(&rejected), None);
+
+        // Holders can cancel their own pending requests
+        let cancelled = client.request_redemption(&user, &300, &290);
+        assert_eq!(client.balance(&user), 300);
+        client.cancel_redemption(&cancelled);
+        assert_eq!(env.auths()[0].0, user);
+        assert_eq!(client.balance(&user), 600);
+        assert_eq!(client.balance(&contract_id), 0);
+        assert_eq!(client.try_cancel_redemption(&cancelled), Err(Ok(Error::UnknownRequest)));
+
+        // Holders can also retire tokens outright
+        client.burn(&user, &100);
+        assert_eq!(client.total_supply(), 500);
+    }
+
+    #[test]
+    fn test_issuance_ceiling() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+        let valuation_id = env.register_contract(None, MockValuation);
+        let valuation = MockValuationClient::new(&env, &valuation_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+
+        client.set_max_supply(&Some(1000));
+        client.mint(&hook, &user, &800);
+        assert_eq!(client.try_mint(&hook, &user, &201), Err(Ok(Error::SupplyCapExceeded)));
+        client.mint(&hook, &user, &200);
+
+        // Coverage of 1.25x: 1500 of present value supports 1200 tokens
+        client.set_max_supply(&None);
+        valuation.set_present_value(&1500);
+        client.set_coverage_guard(&Some(CoverageGuard {
+            oracle: valuation_id.clone(),
+            min_coverage_bps: 12_500,
+        }));
+        client.mint(&hook, &user, &200);
+        assert_eq!(client.try_mint(&hook, &user, &1), Err(Ok(Error::CoverageTooLow)));
+
+        valuation.set_present_value(&3000);
+        client.mint(&hook, &user, &1);
+        assert_eq!(client.total_supply(), 1201);
+    }
+
+    #[test]
+    fn test_ttl_bumping() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+
+        let config = TtlConfig {
+            instance_threshold: 10_000,
+            instance_extend_to: 20_000,
+            persistent_threshold: 10_000,
+            persistent_extend_to: 20_000,
+        };
+        client.set_ttl_config(&config);
+        client.mint(&hook, &user, &1000);
+
+        let balance_ttl = || {
+            env.as_contract(&contract_id, || {
+                env.storage().persistent().get_ttl(&DataKey::Balance(user.clone()))
+            })
+        };
+        assert_eq!(balance_ttl(), 20_000);
+
+        // Below the threshold, a keeper bump restores the full TTL
+        env.ledger().with_mut(|li| li.sequence_number += 15_000);
+        assert_eq!(balance_ttl(), 5_000);
+        client.bump(&user);
+        assert_eq!(balance_ttl(), 20_000);
+
+        let invalid = TtlConfig {
+            persistent_threshold: 30_000,
+            ..config
+        };
+        assert_eq!(client.try_set_ttl_config(&invalid), Err(Ok(Error::InvalidTtl)));
+    }
+
+    #[test]
+    fn test_schema_migration() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, DobToken);
+        let client = DobTokenClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let hook = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(
+            &admin,
+            &hook,
+            &String::from_str(&env, "DOB Token"),
+            &String::from_str(&env, "DOB"),
+            &7,
+            &false,
+        );
+        assert_eq!(client.schema_version(), SCHEMA_VERSION);
+        assert_eq!(client.try_migrate(), Err(Ok(Error::AlreadyMigrated)));
+
+        // Deployments from before versioning migrate up to the current version
+        env.as_contract(&contract_id, || {
+            env.storage().instance().remove(&DataKey::SchemaVersion);
+        });
+        assert_eq!(client.schema_version(), 0);
+        assert_eq!(client.migrate(), SCHEMA_VERSION);
+        assert_eq!(env.auths()[0].0, admin);
+        assert_eq!(client.schema_version(), SCHEMA_VERSION);
     }
 }
@@ -213,6 +213,7 @@ function App() {
                       service={service}
                       poolId={addresses.pool}
                       oracleId={addresses.oracle}
+                      tokenId={addresses.token}
                       userAddress={wallet.publicKey}
                       poolReserves={poolReserves}
                       oracleData={oracleData}
@@ -225,6 +226,7 @@ function App() {
                   <>
                     <OracleManager
                       oracleId={addresses.oracle}
+                      tokenId={addresses.token}
                       data={oracleData}
                       service={service}
                       userAddress={wallet.publicKey}
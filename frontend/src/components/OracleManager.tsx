@@ -6,13 +6,14 @@ import ContractService from '../utils/contracts';
 
 interface OracleManagerProps {
   oracleId: string;
+  tokenId: string;
   data: OracleData | null;
   service: ContractService;
   userAddress: string | null;
   onSuccess?: () => void;
 }
 
-export function OracleManager({ oracleId, data, service, userAddress, onSuccess }: OracleManagerProps) {
+export function OracleManager({ oracleId, tokenId, data, service, userAddress, onSuccess }: OracleManagerProps) {
   const [isEditing, setIsEditing] = useState(false);
   const [fairPriceInput, setFairPriceInput] = useState('');
   const [riskInput, setRiskInput] = useState('');
@@ -24,7 +25,7 @@ export function OracleManager({ oracleId, data, service, userAddress, onSuccess
   const checkUpdater = async () => {
     if (!userAddress) return;
     try {
-      const updater = await service.getOracleUpdater(oracleId);
+      const updater = await service.getOracleUpdater(oracleId, tokenId);
       setIsUpdater(updater === userAddress);
     } catch (err) {
       console.error('Failed to check updater:', err);
@@ -63,7 +64,7 @@ export function OracleManager({ oracleId, data, service, userAddress, onSuccess
 
       console.log('Updating oracle with:', { fairPriceValue, riskValue });
 
-      await service.updateOracle(oracleId, userAddress, fairPriceValue, riskValue);
+      await service.updateOracle(oracleId, tokenId, userAddress, fairPriceValue, riskValue);
 
       setIsEditing(false);
       if (onSuccess) {
@@ -8,6 +8,7 @@ interface SwapInterfaceProps {
   service: ContractService;
   poolId: string;
   oracleId: string;
+  tokenId: string;
   userAddress: string | null;
   poolReserves: PoolReserves | null;
   oracleData: OracleData | null;
@@ -18,6 +19,7 @@ export function SwapInterface({
   service,
   poolId,
   oracleId,
+  tokenId,
   userAddress,
   poolReserves,
   oracleData,
@@ -43,7 +45,7 @@ export function SwapInterface({
       let quote: string;
 
       if (mode === 'buy') {
-        quote = await service.getSwapBuyQuote(oracleId, amount);
+        quote = await service.getSwapBuyQuote(oracleId, tokenId, amount);
       } else {
         quote = await service.getSwapSellQuote(poolId, amount);
       }
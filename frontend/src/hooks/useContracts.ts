@@ -17,12 +17,12 @@ export function useContracts(
 
   const fetchOracleData = useCallback(async () => {
     try {
-      const data = await service.getOracleData(addresses.oracle);
+      const data = await service.getOracleData(addresses.oracle, addresses.token);
       setOracleData(data);
     } catch (err: any) {
       console.error('Failed to fetch oracle data:', err);
     }
-  }, [service, addresses.oracle]);
+  }, [service, addresses.oracle, addresses.token]);
 
   const fetchPoolReserves = useCallback(async () => {
     try {
@@ -98,18 +98,19 @@ export class ContractService {
   }
 
   // Oracle methods
-  async getOracleFairPrice(oracleId: string): Promise<string> {
-    return await this.callContract(oracleId, 'fair_price');
+  // The oracle keys every bond series by its DOB token address
+  async getOracleFairPrice(oracleId: string, tokenId: string): Promise<string> {
+    return await this.callContract(oracleId, 'fair_price', [new Address(tokenId).toScVal()]);
   }
 
-  async getOracleRisk(oracleId: string): Promise<number> {
-    return await this.callContract(oracleId, 'default_risk');
+  async getOracleRisk(oracleId: string, tokenId: string): Promise<number> {
+    return await this.callContract(oracleId, 'default_risk', [new Address(tokenId).toScVal()]);
   }
 
-  async getOracleData(oracleId: string): Promise<OracleData> {
+  async getOracleData(oracleId: string, tokenId: string): Promise<OracleData> {
     const [fairPrice, risk] = await Promise.all([
-      this.getOracleFairPrice(oracleId),
-      this.getOracleRisk(oracleId),
+      this.getOracleFairPrice(oracleId, tokenId),
+      this.getOracleRisk(oracleId, tokenId),
     ]);
 
     return {
@@ -121,6 +122,7 @@ export class ContractService {
 
   async updateOracle(
     oracleId: string,
+    tokenId: string,
     updater: string,
     newFairPrice: string,
     newRisk: string
@@ -136,6 +138,7 @@ export class ContractService {
       .addOperation(
         contract.call(
           'update',
+          new Address(tokenId).toScVal(),
           nativeToScVal(BigInt(newFairPrice), { type: 'i128' }),
           nativeToScVal(parseInt(newRisk), { type: 'u32' })
         )
@@ -171,8 +174,8 @@ export class ContractService {
     }
   }
 
-  async getOracleUpdater(oracleId: string): Promise<string> {
-    return await this.callContract(oracleId, 'updater');
+  async getOracleUpdater(oracleId: string, tokenId: string): Promise<string> {
+    return await this.callContract(oracleId, 'updater', [new Address(tokenId).toScVal()]);
   }
 
   // Token methods
@@ -222,10 +225,10 @@ export class ContractService {
     return nodes.map((node: any) => node);
   }
 
-  async getSwapBuyQuote(oracleId: string, usdcAmount: string): Promise<string> {
+  async getSwapBuyQuote(oracleId: string, tokenId: string, usdcAmount: string): Promise<string> {
     // swap_buy doesn't have a quote function, calculate locally
     // Logic: DEX fee 1%, then 99% to operator, DOB = (operator_amount * 10_000_000) / fair_price
-    const fairPrice = await this.getOracleFairPrice(oracleId);
+    const fairPrice = await this.getOracleFairPrice(oracleId, tokenId);
 
     const usdc = BigInt(usdcAmount);
     // DEX fee = 1%
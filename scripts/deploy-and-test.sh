@@ -113,6 +113,8 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- initialize \
+  --admin $DEPLOYER \
+  --asset $TOKEN_ID \
   --updater $DEPLOYER \
   --initial_fair_price 10000000 \
   --initial_risk 1000 > /dev/null 2>&1
@@ -128,7 +130,8 @@ stellar contract invoke \
   --hook $POOL_ID \
   --name "Dob Solar Farm 2035" \
   --symbol "DOB-35" \
-  --decimals 7 > /dev/null 2>&1
+  --decimals 7 \
+  --compliance_enabled false > /dev/null 2>&1
 
 echo "  Initializing AMM Pool..."
 stellar contract invoke \
@@ -137,6 +140,7 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- initialize \
+  --admin $DEPLOYER \
   --dob_token $TOKEN_ID \
   --usdc_token $USDC_ID \
   --oracle $ORACLE_ID \
@@ -149,6 +153,7 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- initialize \
+  --admin $DEPLOYER \
   --oracle $ORACLE_ID \
   --usdc_token $USDC_ID \
   --dob_token $TOKEN_ID \
@@ -162,6 +167,7 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- initialize \
+  --admin $DEPLOYER \
   --oracle $ORACLE_ID \
   --usdc_token $USDC_ID \
   --dob_token $TOKEN_ID \
@@ -232,6 +238,17 @@ echo -e "${GREEN}✅ Liquid Nodes registered!${NC}"
 echo -e "\n${BLUE}[8/9] Adding initial liquidity to AMM Pool...${NC}"
 
 # First, mint DOB tokens to deployer for liquidity provision
+echo "  Registering deployer as a minter capped at 10,000 DOB..."
+stellar contract invoke \
+  --id $TOKEN_ID \
+  --source deployer \
+  --network $NETWORK \
+  --send=yes \
+  -- set_minter \
+  --minter $DEPLOYER \
+  --mint_cap 100000000000 \
+  --can_burn false > /dev/null 2>&1
+
 echo "  Minting 10,000 DOB for initial liquidity..."
 stellar contract invoke \
   --id $TOKEN_ID \
@@ -239,6 +256,7 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- mint \
+  --minter $DEPLOYER \
   --to $DEPLOYER \
   --amount 100000000000 > /dev/null 2>&1
 
@@ -317,8 +335,9 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- update \
+  --asset $TOKEN_ID \
   --new_fair_price 12000000 \
-  --new_risk 500 > /dev/null 2>&1
+  --new_default_risk 500 > /dev/null 2>&1
 echo -e "  ${GREEN}✅ Oracle updated${NC}"
 
 echo "  Test 8: Buying 500 USDC at new NAV..."
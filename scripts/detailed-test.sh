@@ -49,14 +49,14 @@ get_oracle_nav() {
     stellar contract invoke \
         --id $ORACLE_ID \
         --network $NETWORK \
-        -- nav 2>/dev/null | tr -d '"'
+        -- fair_price --asset $TOKEN_ID 2>/dev/null | tr -d '"'
 }
 
 get_oracle_risk() {
     stellar contract invoke \
         --id $ORACLE_ID \
         --network $NETWORK \
-        -- default_risk 2>/dev/null | tr -d '"'
+        -- default_risk --asset $TOKEN_ID 2>/dev/null | tr -d '"'
 }
 
 echo -e "\n${CYAN}════════════════════════════════════════════════════${NC}"
@@ -187,7 +187,8 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- update \
-  --new_nav $NEW_NAV \
+  --asset $TOKEN_ID \
+  --new_fair_price $NEW_NAV \
   --new_default_risk $NEW_RISK > /dev/null 2>&1
 
 echo -e "\n${GREEN}✅ Oracle actualizado!${NC}"
@@ -44,6 +44,8 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- initialize \
+  --admin $DEPLOYER \
+  --asset $TOKEN_ID \
   --updater $DEPLOYER \
   --initial_fair_price 10000000 \
   --initial_risk 1000
@@ -66,7 +68,8 @@ stellar contract invoke \
   --hook $POOL_ID \
   --name "Dob Solar Farm 2035" \
   --symbol "DOB-35" \
-  --decimals 7
+  --decimals 7 \
+  --compliance_enabled false
 
 if [ $? -ne 0 ]; then
   echo -e "${RED}Failed to initialize DobToken${NC}"
@@ -82,6 +85,7 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- initialize \
+  --admin $DEPLOYER \
   --dob_token $TOKEN_ID \
   --usdc_token $USDC_ID \
   --oracle $ORACLE_ID \
@@ -101,6 +105,7 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- initialize \
+  --admin $DEPLOYER \
   --oracle $ORACLE_ID \
   --usdc_token $USDC_ID \
   --dob_token $TOKEN_ID \
@@ -121,6 +126,7 @@ stellar contract invoke \
   --network $NETWORK \
   --send=yes \
   -- initialize \
+  --admin $DEPLOYER \
   --oracle $ORACLE_ID \
   --usdc_token $USDC_ID \
   --dob_token $TOKEN_ID \
@@ -133,7 +139,8 @@ if [ $? -ne 0 ]; then
 fi
 echo -e "${GREEN}✅ Liquid Node #2 initialized${NC}\n"
 
-# Mint test USDC
+# Mint test USDC; USDC is the deployer-issued Stellar asset, so unlike DobToken
+# mints (which name a registered --minter) the issuer mints directly
 echo -e "${BLUE}[6/8] Minting test USDC...${NC}"
 echo "  Minting 200,000 USDC to deployer..."
 stellar contract invoke \
@@ -7,7 +7,7 @@ echo "=== Simple Stellar Testnet Deployment ==="
 
 # Setup identity
 echo ""
-echo "[1/5] Setting up identity..."
+echo "[1/6] Setting up identity..."
 if ! stellar keys show deployer 2>/dev/null; then
     echo "Creating new identity..."
     stellar keys generate deployer --network testnet
@@ -20,7 +20,7 @@ echo "Deployer: $DEPLOYER"
 
 # Fund account
 echo ""
-echo "[2/5] Funding account..."
+echo "[2/6] Funding account..."
 echo "Requesting funds from Friendbot..."
 curl -s "https://friendbot.stellar.org?addr=$DEPLOYER" | grep -q "successful" && echo "✅ Funded" || echo "⚠️  Check if already funded"
 
@@ -29,7 +29,7 @@ sleep 2
 
 # Deploy one contract as test
 echo ""
-echo "[3/5] Deploying Token contract..."
+echo "[3/6] Deploying Token contract..."
 export NETWORK="testnet"
 
 TOKEN_ID=$(stellar contract deploy \
@@ -47,7 +47,7 @@ fi
 
 # Deploy Oracle
 echo ""
-echo "[4/5] Deploying Oracle..."
+echo "[4/6] Deploying Oracle..."
 ORACLE_ID=$(stellar contract deploy \
   --wasm target/wasm32-unknown-unknown/release/dob_oracle.wasm \
   --source deployer \
@@ -63,15 +63,17 @@ fi
 
 # Initialize Oracle
 echo ""
-echo "[5/5] Initializing Oracle..."
+echo "[5/6] Initializing Oracle..."
 stellar contract invoke \
   --id $ORACLE_ID \
   --source deployer \
   --network $NETWORK \
   --send=yes \
   -- initialize \
+  --admin $DEPLOYER \
+  --asset $TOKEN_ID \
   --updater $DEPLOYER \
-  --initial_nav 10000000 \
+  --initial_fair_price 10000000 \
   --initial_risk 1000
 
 if [ $? -eq 0 ]; then
@@ -81,13 +83,36 @@ else
     exit 1
 fi
 
+# Initialize Token, with the deployer as minter since no market is deployed
+echo ""
+echo "[6/6] Initializing Token..."
+stellar contract invoke \
+  --id $TOKEN_ID \
+  --source deployer \
+  --network $NETWORK \
+  --send=yes \
+  -- initialize \
+  --admin $DEPLOYER \
+  --hook $DEPLOYER \
+  --name "Dob Solar Farm 2035" \
+  --symbol "DOB-35" \
+  --decimals 7 \
+  --compliance_enabled false
+
+if [ $? -eq 0 ]; then
+    echo "✅ Token initialized"
+else
+    echo "❌ Initialization failed!"
+    exit 1
+fi
+
 # Test - check NAV
 echo ""
 echo "Testing: Getting NAV..."
 NAV=$(stellar contract invoke \
   --id $ORACLE_ID \
   --network $NETWORK \
-  -- nav)
+  -- fair_price --asset $TOKEN_ID)
 
 echo "Current NAV: $NAV (expected: 10000000)"
 
@@ -36,11 +36,11 @@ get_reserves() {
 }
 
 get_oracle_nav() {
-    stellar contract invoke --id $ORACLE_ID --network $NETWORK -- nav 2>/dev/null | tr -d '"'
+    stellar contract invoke --id $ORACLE_ID --network $NETWORK -- fair_price --asset $TOKEN_ID 2>/dev/null | tr -d '"'
 }
 
 get_oracle_risk() {
-    stellar contract invoke --id $ORACLE_ID --network $NETWORK -- default_risk 2>/dev/null | tr -d '"'
+    stellar contract invoke --id $ORACLE_ID --network $NETWORK -- default_risk --asset $TOKEN_ID 2>/dev/null | tr -d '"'
 }
 
 echo -e "\n${CYAN}════════════════════════════════════════════════════${NC}"
@@ -57,21 +57,22 @@ fn test_amm_pool_with_open_liquidity() {
         &SorobanString::from_str(&env, "Dob Solar Farm 2035"),
         &SorobanString::from_str(&env, "DOB-35"),
         &7,
+        &false,
     );
 
     // Initialize oracle: NAV = 1.00, Risk = 10%
-    oracle_client.initialize(&admin, &10_000_000, &1000);
+    oracle_client.initialize(&admin, &dob_token_id, &admin, &10_000_000, &1000);
 
     // Initialize AMM Pool
-    amm_pool_client.initialize(&dob_token_id, &usdc_id, &oracle_id, &operator);
+    amm_pool_client.initialize(&admin, &dob_token_id, &usdc_id, &oracle_id, &operator);
 
     // Mint USDC to LP providers
     usdc_client.mint(&lp_provider1, &100_000_0000000); // 100k USDC
     usdc_client.mint(&lp_provider2, &50_000_0000000);  // 50k USDC
 
     // Mint DOB to LP providers (simulate pre-existing tokens)
-    dob_token_client.mint(&lp_provider1, &100_000_0000000); // 100k DOB
-    dob_token_client.mint(&lp_provider2, &50_000_0000000);  // 50k DOB
+    dob_token_client.mint(&amm_pool_id, &lp_provider1, &100_000_0000000); // 100k DOB
+    dob_token_client.mint(&amm_pool_id, &lp_provider2, &50_000_0000000);  // 50k DOB
 
     // LP Provider 1 adds liquidity
     let lp_shares1 = amm_pool_client.add_liquidity(
@@ -114,7 +115,7 @@ fn test_amm_pool_with_open_liquidity() {
 
     // Test sell with sufficient liquidity
     let dob_to_sell = 500_0000000i128; // 500 DOB
-    dob_token_client.mint(&seller, &dob_to_sell);
+    dob_token_client.mint(&amm_pool_id, &seller, &dob_to_sell);
 
     let usdc_received = amm_pool_client.swap_sell(&seller, &dob_to_sell);
 
@@ -169,13 +170,15 @@ fn test_sell_with_liquid_node_fallback() {
         &SorobanString::from_str(&env, "DOB Token"),
         &SorobanString::from_str(&env, "DOB"),
         &7,
+        &false,
     );
 
-    oracle_client.initialize(&admin, &10_000_000, &1000); // NAV=1.00, Risk=10%
+    oracle_client.initialize(&admin, &dob_token_id, &admin, &10_000_000, &1000); // NAV=1.00, Risk=10%
 
-    amm_pool_client.initialize(&dob_token_id, &usdc_id, &oracle_id, &operator);
+    amm_pool_client.initialize(&admin, &dob_token_id, &usdc_id, &oracle_id, &operator);
 
     stabilizer_client.initialize(
+        &admin,
         &oracle_id,
         &usdc_id,
         &dob_token_id,
@@ -186,7 +189,7 @@ fn test_sell_with_liquid_node_fallback() {
     // Add small liquidity to pool (insufficient for large sell)
     let lp_provider = Address::generate(&env);
     usdc_client.mint(&lp_provider, &10_000_0000000); // 10k USDC
-    dob_token_client.mint(&lp_provider, &10_000_0000000);
+    dob_token_client.mint(&amm_pool_id, &lp_provider, &10_000_0000000);
 
     amm_pool_client.add_liquidity(&lp_provider, &10_000_0000000, &10_000_0000000);
 
@@ -203,7 +206,7 @@ fn test_sell_with_liquid_node_fallback() {
 
     // Seller wants to sell large amount (more than pool has)
     let dob_to_sell = 150_000_0000000i128; // 150k DOB
-    dob_token_client.mint(&seller, &dob_to_sell);
+    dob_token_client.mint(&amm_pool_id, &seller, &dob_to_sell);
 
     // Quote the swap (should show it will use liquid nodes)
     let quote = amm_pool_client.quote_swap_sell(&dob_to_sell);
@@ -265,16 +268,17 @@ fn test_multiple_liquid_nodes_competition() {
         &SorobanString::from_str(&env, "DOB Token"),
         &SorobanString::from_str(&env, "DOB"),
         &7,
+        &false,
     );
 
-    oracle_client.initialize(&admin, &10_000_000, &1000); // NAV=1.00, Risk=10% (5% fee)
+    oracle_client.initialize(&admin, &dob_token_id, &admin, &10_000_000, &1000); // NAV=1.00, Risk=10% (5% fee)
 
-    amm_pool_client.initialize(&dob_token_id, &usdc_id, &oracle_id, &operator);
+    amm_pool_client.initialize(&admin, &dob_token_id, &usdc_id, &oracle_id, &operator);
 
     // Initialize all 3 Liquid Nodes
-    ln1_client.initialize(&oracle_id, &usdc_id, &dob_token_id, &ln_operator1, &amm_pool_id);
-    ln2_client.initialize(&oracle_id, &usdc_id, &dob_token_id, &ln_operator2, &amm_pool_id);
-    ln3_client.initialize(&oracle_id, &usdc_id, &dob_token_id, &ln_operator3, &amm_pool_id);
+    ln1_client.initialize(&admin, &oracle_id, &usdc_id, &dob_token_id, &ln_operator1, &amm_pool_id);
+    ln2_client.initialize(&admin, &oracle_id, &usdc_id, &dob_token_id, &ln_operator2, &amm_pool_id);
+    ln3_client.initialize(&admin, &oracle_id, &usdc_id, &dob_token_id, &ln_operator3, &amm_pool_id);
 
     // Fund all Liquid Nodes
     usdc_client.mint(&ln_operator1, &50_000_0000000);
@@ -296,7 +300,7 @@ fn test_multiple_liquid_nodes_competition() {
 
     // Seller sells DOB
     let dob_to_sell = 100_000_0000000i128; // 100k DOB
-    dob_token_client.mint(&seller, &dob_to_sell);
+    dob_token_client.mint(&amm_pool_id, &seller, &dob_to_sell);
 
     let usdc_received = amm_pool_client.swap_sell(&seller, &dob_to_sell);
 
@@ -335,11 +339,12 @@ fn test_liquid_node_registration() {
         &SorobanString::from_str(&env, "DOB"),
         &SorobanString::from_str(&env, "DOB"),
         &7,
+        &false,
     );
 
-    oracle_client.initialize(&admin, &10_000_000, &1000);
-    amm_pool_client.initialize(&dob_token_id, &usdc_id, &oracle_id, &operator);
-    stabilizer_client.initialize(&oracle_id, &usdc_id, &dob_token_id, &ln_operator, &amm_pool_id);
+    oracle_client.initialize(&admin, &dob_token_id, &admin, &10_000_000, &1000);
+    amm_pool_client.initialize(&admin, &dob_token_id, &usdc_id, &oracle_id, &operator);
+    stabilizer_client.initialize(&admin, &oracle_id, &usdc_id, &dob_token_id, &ln_operator, &amm_pool_id);
 
     // Register Liquid Node
     amm_pool_client.register_liquid_node(&stabilizer_id);
@@ -386,10 +391,11 @@ fn test_afterswap_hook_buy() {
         &SorobanString::from_str(&env, "DOB"),
         &SorobanString::from_str(&env, "DOB"),
         &7,
+        &false,
     );
 
-    oracle_client.initialize(&admin, &10_000_000, &1000); // NAV=1.00
-    amm_pool_client.initialize(&dob_token_id, &usdc_id, &oracle_id, &operator);
+    oracle_client.initialize(&admin, &dob_token_id, &admin, &10_000_000, &1000); // NAV=1.00
+    amm_pool_client.initialize(&admin, &dob_token_id, &usdc_id, &oracle_id, &operator);
 
     // Buyer buys DOB
     usdc_client.mint(&buyer, &1_000_0000000); // 1k USDC
@@ -444,20 +450,21 @@ fn test_beforeswap_hook_sell() {
         &SorobanString::from_str(&env, "DOB"),
         &SorobanString::from_str(&env, "DOB"),
         &7,
+        &false,
     );
 
-    oracle_client.initialize(&admin, &10_000_000, &1000); // NAV=1.00, Risk=10%
-    amm_pool_client.initialize(&dob_token_id, &usdc_id, &oracle_id, &operator);
+    oracle_client.initialize(&admin, &dob_token_id, &admin, &10_000_000, &1000); // NAV=1.00, Risk=10%
+    amm_pool_client.initialize(&admin, &dob_token_id, &usdc_id, &oracle_id, &operator);
 
     // Add liquidity to pool
     usdc_client.mint(&lp_provider, &100_000_0000000);
-    dob_token_client.mint(&lp_provider, &100_000_0000000);
+    dob_token_client.mint(&amm_pool_id, &lp_provider, &100_000_0000000);
 
     amm_pool_client.add_liquidity(&lp_provider, &100_000_0000000, &100_000_0000000);
 
     // Seller sells DOB
     let dob_to_sell = 1_000_0000000i128; // 1k DOB
-    dob_token_client.mint(&seller, &dob_to_sell);
+    dob_token_client.mint(&amm_pool_id, &seller, &dob_to_sell);
 
     let (usdc_before, dob_before) = amm_pool_client.get_reserves();
 